@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = aoc2023::day1::part1(input);
+    let _ = aoc2023::day1::part2(input);
+});
@@ -0,0 +1,198 @@
+use anyhow::anyhow;
+use std::path::PathBuf;
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run {
+        day: u8,
+    },
+    Gen {
+        day: u8,
+        size: usize,
+        seed: u64,
+        output: Option<PathBuf>,
+    },
+    New {
+        day: u8,
+    },
+    Fetch {
+        day: u8,
+    },
+    Submit {
+        day: u8,
+        part: u8,
+        answer: Option<String>,
+    },
+    Show {
+        day: u8,
+    },
+    Examples {
+        day: u8,
+    },
+    Watch {
+        day: u8,
+        input: Option<PathBuf>,
+    },
+}
+
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|indx| args.get(indx + 1))
+        .map(|el| el.as_str())
+}
+
+fn parse_day(args: &[String], command: &str) -> anyhow::Result<u8> {
+    let day = args
+        .first()
+        .ok_or(anyhow!("A day must be provided to {}", command))?;
+    Ok(day.parse::<u8>()?)
+}
+
+impl TryFrom<&[String]> for Command {
+    type Error = anyhow::Error;
+    fn try_from(args: &[String]) -> Result<Self, Self::Error> {
+        let Some((first, rest)) = args.split_first() else {
+            return Err(anyhow!("A day must be provided via cli"));
+        };
+        match first.as_str() {
+            "gen" => Ok(Command::Gen {
+                day: parse_day(rest, "gen")?,
+                size: flag_value(rest, "--size")
+                    .ok_or(anyhow!("gen requires --size"))?
+                    .parse::<usize>()?,
+                seed: flag_value(rest, "--seed")
+                    .map(|el| el.parse::<u64>())
+                    .transpose()?
+                    .unwrap_or_default(),
+                output: flag_value(rest, "--output").map(PathBuf::from),
+            }),
+            "new" => Ok(Command::New {
+                day: parse_day(rest, "new")?,
+            }),
+            "fetch" => Ok(Command::Fetch {
+                day: parse_day(rest, "fetch")?,
+            }),
+            "submit" => Ok(Command::Submit {
+                day: parse_day(rest, "submit")?,
+                part: rest
+                    .get(1)
+                    .ok_or(anyhow!("A part must be provided to submit"))?
+                    .parse::<u8>()?,
+                answer: flag_value(rest, "--answer").map(String::from),
+            }),
+            "show" => Ok(Command::Show {
+                day: parse_day(rest, "show")?,
+            }),
+            "examples" => Ok(Command::Examples {
+                day: parse_day(rest, "examples")?,
+            }),
+            "watch" => Ok(Command::Watch {
+                day: parse_day(rest, "watch")?,
+                input: flag_value(rest, "--input").map(PathBuf::from),
+            }),
+            day => Ok(Command::Run {
+                day: day.parse::<u8>()?,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|el| el.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_run() {
+        let command = Command::try_from(args("4").as_slice());
+        assert_eq!(command.unwrap(), Command::Run { day: 4 });
+        assert!(Command::try_from(args("").as_slice()).is_err());
+        assert!(Command::try_from(args("four").as_slice()).is_err());
+    }
+
+    #[test]
+    fn parse_gen() {
+        let command = Command::try_from(args("gen 2 --size 1000 --seed 7").as_slice());
+        assert_eq!(
+            command.unwrap(),
+            Command::Gen {
+                day: 2,
+                size: 1000,
+                seed: 7,
+                output: None
+            }
+        );
+        assert!(Command::try_from(args("gen 2").as_slice()).is_err());
+    }
+
+    #[test]
+    fn parse_new() {
+        let command = Command::try_from(args("new 5").as_slice());
+        assert_eq!(command.unwrap(), Command::New { day: 5 });
+        assert!(Command::try_from(args("new").as_slice()).is_err());
+    }
+
+    #[test]
+    fn parse_fetch() {
+        let command = Command::try_from(args("fetch 12").as_slice());
+        assert_eq!(command.unwrap(), Command::Fetch { day: 12 });
+        assert!(Command::try_from(args("fetch").as_slice()).is_err());
+    }
+
+    #[test]
+    fn parse_submit() {
+        let command = Command::try_from(args("submit 3 2").as_slice());
+        assert_eq!(
+            command.unwrap(),
+            Command::Submit {
+                day: 3,
+                part: 2,
+                answer: None
+            }
+        );
+        let command = Command::try_from(args("submit 3 1 --answer 4361").as_slice());
+        assert_eq!(
+            command.unwrap(),
+            Command::Submit {
+                day: 3,
+                part: 1,
+                answer: Some("4361".to_string())
+            }
+        );
+        assert!(Command::try_from(args("submit 3").as_slice()).is_err());
+    }
+
+    #[test]
+    fn parse_puzzle_commands() {
+        let command = Command::try_from(args("show 7").as_slice());
+        assert_eq!(command.unwrap(), Command::Show { day: 7 });
+        let command = Command::try_from(args("examples 7").as_slice());
+        assert_eq!(command.unwrap(), Command::Examples { day: 7 });
+        assert!(Command::try_from(args("examples").as_slice()).is_err());
+    }
+
+    #[test]
+    fn parse_watch() {
+        let command = Command::try_from(args("watch 3").as_slice());
+        assert_eq!(
+            command.unwrap(),
+            Command::Watch {
+                day: 3,
+                input: None
+            }
+        );
+        let command = Command::try_from(args("watch 3 --input big.txt").as_slice());
+        assert_eq!(
+            command.unwrap(),
+            Command::Watch {
+                day: 3,
+                input: Some(PathBuf::from("big.txt"))
+            }
+        );
+        assert!(Command::try_from(args("watch").as_slice()).is_err());
+    }
+}
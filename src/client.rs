@@ -0,0 +1,304 @@
+use anyhow::anyhow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+// the site asks automated tools to say where they come from, the user agent setting is
+// where users add their own contact
+const DEFAULT_USER_AGENT: &str = concat!(
+    "aoc2023/",
+    env!("CARGO_PKG_VERSION"),
+    " (+",
+    env!("CARGO_PKG_REPOSITORY"),
+    ")"
+);
+const YEAR: u16 = 2023;
+
+#[derive(Debug, PartialEq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+    pub user_agent: String,
+}
+
+impl Config {
+    pub fn path() -> Option<PathBuf> {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or(std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .map(|dir| dir.join("aoc2023").join("config"))
+    }
+
+    pub fn load() -> anyhow::Result<Self> {
+        let file = Self::path()
+            .filter(|path| path.exists())
+            .map(std::fs::read_to_string)
+            .transpose()?;
+        Self::from_sources(|key| std::env::var(key).ok(), file.as_deref())
+    }
+
+    fn from_sources<F>(env: F, file: Option<&str>) -> anyhow::Result<Self>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        // the config file holds `key = value` lines, environment variables override it
+        let mut values = HashMap::new();
+        for line in file.unwrap_or_default().lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(anyhow!("Could not split config line {} at '='", line))?;
+            values.insert(key.trim(), value.trim().to_string());
+        }
+        let setting = |env_key: &str, file_key: &str| {
+            env(env_key)
+                .or(values.get(file_key).cloned())
+                .filter(|el| !el.trim().is_empty())
+        };
+        Ok(Self {
+            session: setting("AOC_SESSION", "session"),
+            base_url: setting("AOC_BASE_URL", "base_url")
+                .unwrap_or(DEFAULT_BASE_URL.to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            user_agent: setting("AOC_USER_AGENT", "user_agent")
+                .unwrap_or(DEFAULT_USER_AGENT.to_string()),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FetchedInput {
+    pub path: PathBuf,
+    pub downloaded: bool,
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    config: Config,
+}
+
+impl AocClient {
+    pub fn new(config: Config) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(&config.user_agent)
+            .timeout(Duration::from_secs(30))
+            .build();
+        Self { agent, config }
+    }
+
+    fn session(&self) -> anyhow::Result<&str> {
+        self.config.session.as_deref().ok_or(anyhow!(
+            "No session token: set AOC_SESSION or add `session = <token>` to {}",
+            Config::path()
+                .map(|path| path.display().to_string())
+                .unwrap_or("the config file".to_string())
+        ))
+    }
+
+    fn day_url(&self, day: u8) -> anyhow::Result<String> {
+        if !(1..=25).contains(&day) {
+            return Err(anyhow!("{} is not a valid day value", day));
+        }
+        Ok(format!("{}/{}/day/{}", self.config.base_url, YEAR, day))
+    }
+
+    fn request(&self, method: &str, url: &str) -> anyhow::Result<ureq::Request> {
+        let cookie = format!("session={}", self.session()?);
+        Ok(self.agent.request(method, url).set("Cookie", &cookie))
+    }
+
+    fn read(
+        response: Result<ureq::Response, ureq::Error>,
+        url: &str,
+        day: u8,
+    ) -> anyhow::Result<String> {
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(404, _)) => {
+                Err(anyhow!("Day {} is not available yet at {}", day, url))
+            }
+            Err(ureq::Error::Status(400 | 401 | 500, _)) => Err(anyhow!(
+                "{} rejected the request, the session token has likely expired",
+                url
+            )),
+            Err(ureq::Error::Status(status, _)) => {
+                Err(anyhow!("Request to {} failed with status {}", url, status))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn fetch_puzzle(&self, day: u8) -> anyhow::Result<String> {
+        let url = self.day_url(day)?;
+        Self::read(self.request("GET", &url)?.call(), &url, day)
+    }
+
+    pub fn fetch_input(&self, day: u8) -> anyhow::Result<String> {
+        let url = format!("{}/input", self.day_url(day)?);
+        Self::read(self.request("GET", &url)?.call(), &url, day)
+    }
+
+    pub fn submit_answer(&self, day: u8, part: u8, answer: &str) -> anyhow::Result<String> {
+        let url = format!("{}/answer", self.day_url(day)?);
+        let level = part.to_string();
+        let form = [("level", level.as_str()), ("answer", answer)];
+        Self::read(self.request("POST", &url)?.send_form(&form), &url, day)
+    }
+
+    pub fn fetch_input_cached(&self, input_dir: &Path, day: u8) -> anyhow::Result<FetchedInput> {
+        // inputs never change, so anything already on disk is never downloaded again
+        let path = input_dir.join(format!("day{day}.txt"));
+        let cached = std::fs::metadata(&path).is_ok_and(|el| el.len() > 0);
+        if cached {
+            return Ok(FetchedInput {
+                path,
+                downloaded: false,
+            });
+        }
+        let input = self.fetch_input(day)?;
+        std::fs::create_dir_all(input_dir)?;
+        std::fs::write(&path, input)?;
+        Ok(FetchedInput {
+            path,
+            downloaded: true,
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::thread::JoinHandle;
+
+    #[derive(Debug)]
+    pub struct Recorded {
+        pub method: String,
+        pub url: String,
+        pub headers: HashMap<String, String>,
+        pub body: String,
+    }
+
+    pub fn serve(responses: Vec<(u16, &str)>) -> (String, JoinHandle<Vec<Recorded>>) {
+        // answers one request per canned response, in order, and hands back what it saw
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr());
+        let responses = responses
+            .into_iter()
+            .map(|(status, body)| (status, body.to_string()))
+            .collect::<Vec<(u16, String)>>();
+        let handle = std::thread::spawn(move || {
+            let mut recorded = vec![];
+            for (status, body) in responses {
+                let mut request = server.recv().unwrap();
+                let mut request_body = String::new();
+                request
+                    .as_reader()
+                    .read_to_string(&mut request_body)
+                    .unwrap();
+                recorded.push(Recorded {
+                    method: request.method().to_string(),
+                    url: request.url().to_string(),
+                    headers: request
+                        .headers()
+                        .iter()
+                        .map(|el| (el.field.to_string().to_lowercase(), el.value.to_string()))
+                        .collect(),
+                    body: request_body,
+                });
+                let response = tiny_http::Response::from_string(body).with_status_code(status);
+                request.respond(response).unwrap();
+            }
+            recorded
+        });
+        (base_url, handle)
+    }
+
+    pub fn test_client(base_url: &str) -> AocClient {
+        AocClient::new(Config {
+            session: Some("53cr3t".to_string()),
+            base_url: base_url.to_string(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+        })
+    }
+
+    pub fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc2023-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn config_sources() {
+        let file = "# personal settings\nsession = from-file\nbase_url = http://localhost:8080/\n";
+        let env = |key: &str| (key == "AOC_SESSION").then(|| "from-env".to_string());
+        let config = Config::from_sources(env, Some(file)).unwrap();
+        assert_eq!(config.session.as_deref(), Some("from-env"));
+        assert_eq!(config.base_url, "http://localhost:8080");
+        assert_eq!(config.user_agent, DEFAULT_USER_AGENT);
+        let config = Config::from_sources(|_| None, None).unwrap();
+        assert_eq!(config.session, None);
+        assert_eq!(config.base_url, DEFAULT_BASE_URL);
+        assert!(Config::from_sources(|_| None, Some("session")).is_err());
+    }
+
+    #[test]
+    fn user_agent_names_the_project() {
+        assert_eq!(
+            DEFAULT_USER_AGENT,
+            concat!(
+                "aoc2023/",
+                env!("CARGO_PKG_VERSION"),
+                " (+https://github.com/nicflower/aoc2023)"
+            )
+        );
+    }
+
+    #[test]
+    fn fetch_sends_session_and_user_agent() {
+        let (base_url, server) = serve(vec![(200, "1abc2\n")]);
+        let input = test_client(&base_url).fetch_input(1).unwrap();
+        assert_eq!(input, "1abc2\n");
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].url, "/2023/day/1/input");
+        assert_eq!(requests[0].headers["cookie"], "session=53cr3t");
+        assert_eq!(requests[0].headers["user-agent"], DEFAULT_USER_AGENT);
+    }
+
+    #[test]
+    fn fetch_downloads_once() {
+        let dir = temp_dir("fetch");
+        let (base_url, server) = serve(vec![(200, "467..114..\n")]);
+        let client = test_client(&base_url);
+        let first = client.fetch_input_cached(&dir, 3).unwrap();
+        assert!(first.downloaded);
+        assert_eq!(server.join().unwrap().len(), 1);
+        // the server is gone, so this only succeeds from the cache
+        let second = client.fetch_input_cached(&dir, 3).unwrap();
+        assert!(!second.downloaded);
+        assert_eq!(
+            std::fs::read_to_string(second.path).unwrap(),
+            "467..114..\n"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn fetch_errors() {
+        let (base_url, server) = serve(vec![(404, "Not yet"), (400, "Bad session")]);
+        let client = test_client(&base_url);
+        let locked = client.fetch_input(25).unwrap_err().to_string();
+        assert!(locked.contains("not available yet"), "{}", locked);
+        let rejected = client.fetch_input(1).unwrap_err().to_string();
+        assert!(rejected.contains("session token"), "{}", rejected);
+        server.join().unwrap();
+        assert!(client.fetch_input(26).is_err());
+        let anonymous = AocClient::new(Config::from_sources(|_| None, None).unwrap());
+        assert!(anonymous.fetch_input(1).is_err());
+    }
+}
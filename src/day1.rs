@@ -1,20 +1,15 @@
-use std::path::Path;
-
-pub async fn part1(input_file: &Path) -> anyhow::Result<u64> {
-    let content = tokio::fs::read_to_string(input_file).await?;
+pub fn part1(content: &str) -> anyhow::Result<u64> {
     let sum = content.lines().fold(0, |acc, line| {
         let calib_number = line_to_calib_num(line).unwrap_or(0);
         acc + calib_number
     });
     Ok(sum)
 }
-pub async fn part2(input_file: &Path) -> anyhow::Result<u64> {
-    let content = tokio::fs::read_to_string(input_file).await?;
+pub fn part2(content: &str) -> anyhow::Result<u64> {
     let sum = content
         .lines()
-        .map(|line| map_spelled_digits(line))
-        .filter(|res| res.is_ok())
-        .map(|el| el.unwrap())
+        .map(map_spelled_digits)
+        .filter_map(|el| el.ok())
         .fold(0, |acc, line| {
             let calib_number = line_to_calib_num(&line).unwrap_or(0);
             acc + calib_number
@@ -23,7 +18,7 @@ pub async fn part2(input_file: &Path) -> anyhow::Result<u64> {
 }
 
 fn line_to_calib_num(line: &str) -> anyhow::Result<u64> {
-    let digits: Vec<char> = line.chars().filter(|c| c.is_numeric()).collect();
+    let digits: Vec<char> = line.chars().filter(|c| c.is_ascii_digit()).collect();
     let num = match digits.len() {
         0 => 0,
         1 => format!("{}{}", digits.first().unwrap(), digits.first().unwrap()).parse::<u64>()?,
@@ -45,31 +40,14 @@ const DIGITS_SPELL: &[(&str, char)] = &[
 ];
 
 fn map_spelled_digits(line: &str) -> anyhow::Result<String> {
-    let mut out_indx = 0;
-    // find spelled digits
+    // find spelled digits, walking char boundaries so multi-byte chars never get split
     let mut ret_str = String::with_capacity(line.len());
-    while out_indx < line.chars().count() {
-        let digit = {
-            let mut found_digit = None;
-            for digit in DIGITS_SPELL {
-                if out_indx + digit.0.len() <= line.len()
-                    && &line[out_indx..out_indx + digit.0.len()] == digit.0
-                {
-                    found_digit = Some(digit.1);
-                    break;
-                }
-            }
-            found_digit
-        };
-        match digit {
-            Some(d) => {
-                ret_str.push(d);
-            }
-            None => {
-                ret_str.push(line.chars().nth(out_indx).unwrap());
-            }
-        };
-        out_indx += 1;
+    for (out_indx, c) in line.char_indices() {
+        let digit = DIGITS_SPELL
+            .iter()
+            .find(|digit| line[out_indx..].starts_with(digit.0))
+            .map(|digit| digit.1);
+        ret_str.push(digit.unwrap_or(c));
     }
     Ok(ret_str)
 }
@@ -77,6 +55,7 @@ fn map_spelled_digits(line: &str) -> anyhow::Result<String> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use proptest::prelude::*;
     #[test]
     fn calib_no_digit() {
         let calib_num_res = line_to_calib_num("asdfg");
@@ -114,4 +93,56 @@ mod test {
         assert!(map_spelled.is_ok());
         assert_eq!(map_spelled.unwrap(), "as7even231ne2wo8ight");
     }
+    #[test]
+    fn map_spelled_multibyte() {
+        let map_spelled = map_spelled_digits("éone2ü");
+        assert!(map_spelled.is_ok());
+        assert_eq!(map_spelled.unwrap(), "é1ne2ü");
+    }
+
+    fn naive_calib_num(line: &str, spelled: bool) -> u64 {
+        // checks every position for a digit or, if requested, a spelled digit starting there
+        let digits = (0..line.len())
+            .filter_map(|indx| {
+                let c = line.as_bytes()[indx];
+                if c.is_ascii_digit() {
+                    return Some((c - b'0') as u64);
+                }
+                if !spelled {
+                    return None;
+                }
+                DIGITS_SPELL
+                    .iter()
+                    .position(|(word, _)| line[indx..].starts_with(word))
+                    .map(|pos| pos as u64 + 1)
+            })
+            .collect::<Vec<u64>>();
+        match (digits.first(), digits.last()) {
+            (Some(first), Some(last)) => first * 10 + last,
+            _ => 0,
+        }
+    }
+
+    fn calibration_line() -> impl Strategy<Value = String> {
+        let chunk = prop_oneof![
+            "[a-z]{1,3}",
+            "[0-9]",
+            proptest::sample::select(DIGITS_SPELL.iter().map(|el| el.0).collect::<Vec<&str>>())
+                .prop_map(|el| el.to_string()),
+        ];
+        proptest::collection::vec(chunk, 0..10).prop_map(|chunks| chunks.concat())
+    }
+
+    proptest! {
+        #[test]
+        fn calib_matches_naive(line in calibration_line()) {
+            prop_assert_eq!(line_to_calib_num(&line).unwrap(), naive_calib_num(&line, false));
+        }
+
+        #[test]
+        fn spelled_calib_matches_naive(line in calibration_line()) {
+            let mapped = map_spelled_digits(&line).unwrap();
+            prop_assert_eq!(line_to_calib_num(&mapped).unwrap(), naive_calib_num(&line, true));
+        }
+    }
 }
@@ -0,0 +1,176 @@
+use crate::grid::{Direction, Grid, Position};
+use anyhow::anyhow;
+
+pub fn part1(content: &str) -> anyhow::Result<usize> {
+    let maze = PipeMaze::try_from(content)?;
+    let pipe_loop = maze.find_loop()?;
+    Ok(pipe_loop.len() / 2)
+}
+pub fn part2(content: &str) -> anyhow::Result<usize> {
+    let maze = PipeMaze::try_from(content)?;
+    let pipe_loop = maze.find_loop()?;
+    Ok(enclosed_tiles(&pipe_loop))
+}
+
+const PIPES: &[char] = &['|', '-', 'L', 'J', '7', 'F'];
+
+fn pipe_connections(tile: char) -> Option<[Direction; 2]> {
+    match tile {
+        '|' => Some([Direction::North, Direction::South]),
+        '-' => Some([Direction::East, Direction::West]),
+        'L' => Some([Direction::North, Direction::East]),
+        'J' => Some([Direction::North, Direction::West]),
+        '7' => Some([Direction::South, Direction::West]),
+        'F' => Some([Direction::South, Direction::East]),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+struct PipeMaze {
+    grid: Grid<char>,
+    start: Position,
+}
+
+impl PipeMaze {
+    fn connects(&self, pos: Position, direction: Direction) -> bool {
+        self.grid
+            .neighbour(pos, direction)
+            .and_then(|next| self.grid.get(next))
+            .and_then(|tile| pipe_connections(*tile))
+            .is_some_and(|connections| connections.contains(&direction.opposite()))
+    }
+
+    pub fn start_shape(&self) -> anyhow::Result<char> {
+        let connected = Direction::ALL
+            .into_iter()
+            .filter(|dir| self.connects(self.start, *dir))
+            .collect::<Vec<Direction>>();
+        if connected.len() != 2 {
+            return Err(anyhow!(
+                "Expected 2 pipes connected to the start tile, found {}",
+                connected.len()
+            ));
+        }
+        PIPES
+            .iter()
+            .copied()
+            .find(|pipe| {
+                pipe_connections(*pipe)
+                    .is_some_and(|connections| connected.iter().all(|d| connections.contains(d)))
+            })
+            .ok_or(anyhow!("No pipe matches the start connections"))
+    }
+
+    pub fn find_loop(&self) -> anyhow::Result<Vec<Position>> {
+        let start_shape = self.start_shape()?;
+        let mut direction =
+            pipe_connections(start_shape).ok_or(anyhow!("{} is not a pipe", start_shape))?[0];
+        let mut pos = self.start;
+        let mut pipe_loop = vec![];
+        loop {
+            pipe_loop.push(pos);
+            pos = self
+                .grid
+                .neighbour(pos, direction)
+                .ok_or(anyhow!("The loop leaves the maze at {:?}", pos))?;
+            if pos == self.start {
+                break;
+            }
+            let tile = self
+                .grid
+                .get(pos)
+                .ok_or(anyhow!("Position {:?} is outside the maze", pos))?;
+            let [first, second] = pipe_connections(*tile).ok_or(anyhow!(
+                "Tile {} at {:?} is not a pipe",
+                tile,
+                pos
+            ))?;
+            let came_from = direction.opposite();
+            direction = if first == came_from {
+                second
+            } else if second == came_from {
+                first
+            } else {
+                return Err(anyhow!("Tile {} at {:?} breaks the loop", tile, pos));
+            };
+        }
+        Ok(pipe_loop)
+    }
+}
+
+impl TryFrom<&str> for PipeMaze {
+    type Error = anyhow::Error;
+    fn try_from(content: &str) -> Result<Self, Self::Error> {
+        let grid = Grid::try_from(content)?;
+        let start = grid
+            .find(|tile| *tile == 'S')
+            .ok_or(anyhow!("Could not find the start tile"))?;
+        Ok(Self { grid, start })
+    }
+}
+
+fn enclosed_tiles(pipe_loop: &[Position]) -> usize {
+    // shoelace formula for the loop area, then Pick's theorem (A = i + b/2 - 1)
+    // to count the tiles strictly inside it
+    let double_area = pipe_loop
+        .iter()
+        .zip(pipe_loop.iter().cycle().skip(1))
+        .map(|(a, b)| a.col as i64 * b.row as i64 - b.col as i64 * a.row as i64)
+        .sum::<i64>()
+        .unsigned_abs() as usize;
+    // a loop hugging itself has no inside, so keep the subtraction from underflowing
+    (double_area + 2).saturating_sub(pipe_loop.len()) / 2
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const SIMPLE_LOOP: &str = ".....\n.S-7.\n.|.|.\n.L-J.\n.....";
+    const COMPLEX_LOOP: &str = "..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...";
+
+    #[test]
+    fn infer_start_shape() {
+        let maze = PipeMaze::try_from(SIMPLE_LOOP).unwrap();
+        assert_eq!(maze.start_shape().unwrap(), 'F');
+        let maze = PipeMaze::try_from(COMPLEX_LOOP).unwrap();
+        assert_eq!(maze.start_shape().unwrap(), 'F');
+    }
+
+    #[test]
+    fn farthest_distance() {
+        let maze = PipeMaze::try_from(SIMPLE_LOOP).unwrap();
+        assert_eq!(maze.find_loop().unwrap().len() / 2, 4);
+        let maze = PipeMaze::try_from(COMPLEX_LOOP).unwrap();
+        assert_eq!(maze.find_loop().unwrap().len() / 2, 8);
+    }
+
+    #[test]
+    fn count_enclosed_tiles() {
+        let content = "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+        let maze = PipeMaze::try_from(content).unwrap();
+        assert_eq!(enclosed_tiles(&maze.find_loop().unwrap()), 4);
+
+        let content = ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+        let maze = PipeMaze::try_from(content).unwrap();
+        assert_eq!(enclosed_tiles(&maze.find_loop().unwrap()), 8);
+    }
+}
@@ -0,0 +1,133 @@
+use anyhow::anyhow;
+
+pub fn part1(content: &str) -> anyhow::Result<u64> {
+    part_with_factor(content, 2)
+}
+pub fn part2(content: &str) -> anyhow::Result<u64> {
+    part_with_factor(content, 1_000_000)
+}
+pub fn part_with_factor(content: &str, factor: u64) -> anyhow::Result<u64> {
+    // an empty line grows to `factor` lines, it can't vanish altogether
+    if factor == 0 {
+        return Err(anyhow!("The expansion factor must be at least 1"));
+    }
+    let image = GalaxyImage::try_from(content)?;
+    Ok(image.sum_of_distances(factor))
+}
+
+#[derive(Debug, PartialEq)]
+struct GalaxyImage {
+    width: usize,
+    height: usize,
+    galaxies: Vec<(usize, usize)>,
+}
+
+impl GalaxyImage {
+    fn expanded_coordinates(coordinates: &[usize], size: usize, factor: u64) -> Vec<u64> {
+        // for every original index, its index once the empty lines before it are expanded
+        let mut expanded = Vec::with_capacity(size);
+        let mut offset = 0;
+        for indx in 0..size {
+            expanded.push(indx as u64 + offset);
+            if !coordinates.contains(&indx) {
+                offset += factor - 1;
+            }
+        }
+        expanded
+    }
+
+    pub fn expanded_galaxies(&self, factor: u64) -> Vec<(u64, u64)> {
+        let rows = self.galaxies.iter().map(|el| el.0).collect::<Vec<usize>>();
+        let cols = self.galaxies.iter().map(|el| el.1).collect::<Vec<usize>>();
+        let expanded_rows = Self::expanded_coordinates(&rows, self.height, factor);
+        let expanded_cols = Self::expanded_coordinates(&cols, self.width, factor);
+        self.galaxies
+            .iter()
+            .map(|(row, col)| (expanded_rows[*row], expanded_cols[*col]))
+            .collect()
+    }
+
+    pub fn sum_of_distances(&self, factor: u64) -> u64 {
+        let galaxies = self.expanded_galaxies(factor);
+        galaxies
+            .iter()
+            .enumerate()
+            .flat_map(|(indx, a)| {
+                galaxies[indx + 1..]
+                    .iter()
+                    .map(move |b| a.0.abs_diff(b.0) + a.1.abs_diff(b.1))
+            })
+            .sum()
+    }
+}
+
+impl TryFrom<&str> for GalaxyImage {
+    type Error = anyhow::Error;
+    fn try_from(content: &str) -> Result<Self, Self::Error> {
+        let mut galaxies = vec![];
+        let mut width = 0;
+        let mut height = 0;
+        for (row, line) in content.lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                match c {
+                    '#' => galaxies.push((row, col)),
+                    '.' => {}
+                    _ => return Err(anyhow!("Unexpected character {} in {}", c, line)),
+                }
+            }
+            width = width.max(line.chars().count());
+            height = row + 1;
+        }
+        Ok(Self {
+            width,
+            height,
+            galaxies,
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    #[test]
+    fn parse_image() {
+        let image = GalaxyImage::try_from(EXAMPLE).unwrap();
+        assert_eq!(image.galaxies.len(), 9);
+        assert_eq!(image.galaxies[0], (0, 3));
+        assert_eq!((image.width, image.height), (10, 10));
+    }
+
+    #[test]
+    fn expand_galaxies() {
+        let image = GalaxyImage::try_from(EXAMPLE).unwrap();
+        let expanded = image.expanded_galaxies(2);
+        assert_eq!(expanded[0], (0, 4));
+        assert_eq!(expanded[8], (11, 5));
+    }
+
+    #[test]
+    fn distances_with_factor() {
+        let image = GalaxyImage::try_from(EXAMPLE).unwrap();
+        assert_eq!(image.sum_of_distances(2), 374);
+        assert_eq!(image.sum_of_distances(10), 1030);
+        assert_eq!(image.sum_of_distances(100), 8410);
+    }
+
+    #[test]
+    fn zero_factor() {
+        assert!(part_with_factor(EXAMPLE, 0).is_err());
+        assert_eq!(part_with_factor(EXAMPLE, 1).unwrap(), 292);
+    }
+}
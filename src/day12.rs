@@ -0,0 +1,160 @@
+use anyhow::anyhow;
+
+pub fn part1(content: &str) -> anyhow::Result<u64> {
+    let records = content
+        .lines()
+        .map(ConditionRecord::try_from_line)
+        .collect::<Result<Vec<ConditionRecord>, _>>()?;
+    records
+        .iter()
+        .try_fold(0_u64, |acc, el| checked_total(acc, el.arrangements()?))
+}
+pub fn part2(content: &str) -> anyhow::Result<u64> {
+    let records = content
+        .lines()
+        .map(ConditionRecord::try_from_line)
+        .collect::<Result<Vec<ConditionRecord>, _>>()?;
+    records.iter().try_fold(0_u64, |acc, el| {
+        checked_total(acc, el.unfold(5).arrangements()?)
+    })
+}
+
+fn checked_total(acc: u64, arrangements: u64) -> anyhow::Result<u64> {
+    acc.checked_add(arrangements)
+        .ok_or(anyhow!("Total arrangements overflow"))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ConditionRecord {
+    springs: Vec<char>,
+    groups: Vec<usize>,
+}
+
+impl ConditionRecord {
+    pub fn new(springs: Vec<char>, groups: Vec<usize>) -> Self {
+        Self { springs, groups }
+    }
+
+    pub fn arrangements(&self) -> anyhow::Result<u64> {
+        count_arrangements(&self.springs, &self.groups).ok_or(anyhow!(
+            "Arrangements of {} overflow",
+            self.springs.iter().collect::<String>()
+        ))
+    }
+
+    pub fn unfold(&self, times: usize) -> Self {
+        let mut springs = Vec::with_capacity((self.springs.len() + 1) * times);
+        for indx in 0..times {
+            if indx > 0 {
+                springs.push('?');
+            }
+            springs.extend_from_slice(&self.springs);
+        }
+        Self::new(springs, self.groups.repeat(times))
+    }
+
+    fn try_from_line(line: &str) -> anyhow::Result<Self> {
+        let (springs_str, groups_str) = line
+            .split_once(' ')
+            .ok_or(anyhow!("Could not split {} at ' '", line))?;
+        let springs = springs_str
+            .chars()
+            .map(|c| match c {
+                '.' | '#' | '?' => Ok(c),
+                _ => Err(anyhow!("Unexpected spring {} in {}", c, line)),
+            })
+            .collect::<Result<Vec<char>, _>>()?;
+        let groups = groups_str
+            .split(',')
+            .map(|el| el.trim().parse::<usize>())
+            .collect::<Result<Vec<usize>, _>>()?;
+        if groups.contains(&0) {
+            return Err(anyhow!("Empty damaged group in {}", line));
+        }
+        Ok(Self::new(springs, groups))
+    }
+}
+
+pub fn count_arrangements(springs: &[char], groups: &[usize]) -> Option<u64> {
+    // arrangements[i][j] holds the number of ways springs[i..] can match groups[j..]
+    let (n, m) = (springs.len(), groups.len());
+    let mut arrangements = vec![vec![0_u64; m + 1]; n + 2];
+    arrangements[n][m] = 1;
+    arrangements[n + 1][m] = 1;
+    for i in (0..n).rev() {
+        for j in 0..=m {
+            let mut ways = 0;
+            if springs[i] != '#' {
+                ways += arrangements[i + 1][j];
+            }
+            if springs[i] != '.' && j < m {
+                let end = i.saturating_add(groups[j]);
+                let fits = end <= n
+                    && springs[i..end].iter().all(|c| *c != '.')
+                    && springs.get(end) != Some(&'#');
+                if fits {
+                    ways = ways.checked_add(arrangements[end + 1][j + 1])?;
+                }
+            }
+            arrangements[i][j] = ways;
+        }
+    }
+    Some(arrangements[0][0])
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const EXAMPLE: &[(&str, u64, u64)] = &[
+        ("???.### 1,1,3", 1, 1),
+        (".??..??...?##. 1,1,3", 4, 16384),
+        ("?#?#?#?#?#?#?#? 1,3,1,6", 1, 1),
+        ("????.#...#... 4,1,1", 1, 16),
+        ("????.######..#####. 1,6,5", 4, 2500),
+        ("?###???????? 3,2,1", 10, 506250),
+    ];
+
+    #[test]
+    fn parse_line() {
+        let res = ConditionRecord::try_from_line("???.### 1,1,3");
+        assert!(
+            res.is_ok(),
+            "Failed to parse ConditionRecord from line: {}",
+            res.unwrap_err()
+        );
+        let record = res.unwrap();
+        assert_eq!(record.springs, ['?', '?', '?', '.', '#', '#', '#']);
+        assert_eq!(record.groups, [1, 1, 3]);
+    }
+
+    #[test]
+    fn unfold_record() {
+        let record = ConditionRecord::try_from_line(".# 1").unwrap();
+        let unfolded = record.unfold(5);
+        assert_eq!(
+            unfolded.springs.iter().collect::<String>(),
+            ".#?.#?.#?.#?.#"
+        );
+        assert_eq!(unfolded.groups, [1, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn count_example_rows() {
+        for (line, folded, unfolded) in EXAMPLE {
+            let record = ConditionRecord::try_from_line(line).unwrap();
+            assert_eq!(
+                count_arrangements(&record.springs, &record.groups).unwrap(),
+                *folded,
+                "{}",
+                line
+            );
+            assert_eq!(
+                record.unfold(5).arrangements().unwrap(),
+                *unfolded,
+                "{}",
+                line
+            );
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::grid::{Grid, Position};
+use anyhow::anyhow;
+
+pub fn part1(content: &str) -> anyhow::Result<usize> {
+    let patterns = parse_patterns(content)?;
+    patterns
+        .iter()
+        .map(|pattern| reflection_summary(pattern, 0))
+        .sum()
+}
+pub fn part2(content: &str) -> anyhow::Result<usize> {
+    let patterns = parse_patterns(content)?;
+    patterns
+        .iter()
+        .map(|pattern| reflection_summary(pattern, 1))
+        .sum()
+}
+
+fn parse_patterns(content: &str) -> anyhow::Result<Vec<Grid<char>>> {
+    content
+        .replace("\r\n", "\n")
+        .split("\n\n")
+        .filter(|block| !block.trim().is_empty())
+        .map(Grid::try_from)
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Reflection {
+    Horizontal(usize),
+    Vertical(usize),
+}
+
+impl Reflection {
+    pub fn summary(&self) -> usize {
+        match self {
+            Reflection::Horizontal(rows_above) => 100 * rows_above,
+            Reflection::Vertical(cols_left) => *cols_left,
+        }
+    }
+}
+
+fn mismatches_across_row(pattern: &Grid<char>, row: usize) -> usize {
+    // counts the differing cells when folding the pattern between row - 1 and row
+    (0..row.min(pattern.height() - row))
+        .flat_map(|offset| {
+            (0..pattern.width()).map(move |col| {
+                (
+                    Position::new(row - 1 - offset, col),
+                    Position::new(row + offset, col),
+                )
+            })
+        })
+        .filter(|(a, b)| pattern.get(*a) != pattern.get(*b))
+        .count()
+}
+
+fn mismatches_across_col(pattern: &Grid<char>, col: usize) -> usize {
+    (0..col.min(pattern.width() - col))
+        .flat_map(|offset| {
+            (0..pattern.height()).map(move |row| {
+                (
+                    Position::new(row, col - 1 - offset),
+                    Position::new(row, col + offset),
+                )
+            })
+        })
+        .filter(|(a, b)| pattern.get(*a) != pattern.get(*b))
+        .count()
+}
+
+fn find_reflection(pattern: &Grid<char>, differences: usize) -> Option<Reflection> {
+    (1..pattern.height())
+        .find(|row| mismatches_across_row(pattern, *row) == differences)
+        .map(Reflection::Horizontal)
+        .or_else(|| {
+            (1..pattern.width())
+                .find(|col| mismatches_across_col(pattern, *col) == differences)
+                .map(Reflection::Vertical)
+        })
+}
+
+fn reflection_summary(pattern: &Grid<char>, differences: usize) -> anyhow::Result<usize> {
+    find_reflection(pattern, differences)
+        .map(|reflection| reflection.summary())
+        .ok_or(anyhow!(
+            "No reflection with {} differences found in pattern",
+            differences
+        ))
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
+
+    #[test]
+    fn split_patterns() {
+        let patterns = parse_patterns(EXAMPLE).unwrap();
+        assert_eq!(patterns.len(), 2);
+        assert_eq!(patterns[0].height(), 7);
+        assert_eq!(patterns[1].width(), 9);
+    }
+
+    #[test]
+    fn exact_reflections() {
+        let patterns = parse_patterns(EXAMPLE).unwrap();
+        assert_eq!(
+            find_reflection(&patterns[0], 0),
+            Some(Reflection::Vertical(5))
+        );
+        assert_eq!(
+            find_reflection(&patterns[1], 0),
+            Some(Reflection::Horizontal(4))
+        );
+    }
+
+    #[test]
+    fn smudged_reflections() {
+        let patterns = parse_patterns(EXAMPLE).unwrap();
+        assert_eq!(
+            find_reflection(&patterns[0], 1),
+            Some(Reflection::Horizontal(3))
+        );
+        assert_eq!(
+            find_reflection(&patterns[1], 1),
+            Some(Reflection::Horizontal(1))
+        );
+        let summary = patterns
+            .iter()
+            .map(|pattern| reflection_summary(pattern, 1))
+            .sum::<anyhow::Result<usize>>();
+        assert_eq!(summary.unwrap(), 400);
+    }
+}
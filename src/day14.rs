@@ -0,0 +1,208 @@
+use crate::grid::{Direction, Grid, Position};
+use anyhow::anyhow;
+use std::collections::HashMap;
+
+pub fn part1(content: &str) -> anyhow::Result<usize> {
+    let mut platform = Platform::try_from(content)?;
+    platform.tilt(Direction::North);
+    Ok(platform.north_load())
+}
+pub fn part2(content: &str) -> anyhow::Result<usize> {
+    let mut platform = Platform::try_from(content)?;
+    platform.spin_cycles(1_000_000_000);
+    Ok(platform.north_load())
+}
+
+const ROUND_ROCK: char = 'O';
+const CUBE_ROCK: char = '#';
+const EMPTY: char = '.';
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Platform {
+    grid: Grid<char>,
+}
+
+impl Platform {
+    fn lanes(&self, direction: Direction) -> Vec<Vec<Position>> {
+        // each lane lists its positions starting from the edge the rocks roll towards
+        let (width, height) = (self.grid.width(), self.grid.height());
+        match direction {
+            Direction::North => (0..width)
+                .map(|col| (0..height).map(|row| Position::new(row, col)).collect())
+                .collect(),
+            Direction::South => (0..width)
+                .map(|col| {
+                    (0..height)
+                        .rev()
+                        .map(|row| Position::new(row, col))
+                        .collect()
+                })
+                .collect(),
+            Direction::West => (0..height)
+                .map(|row| (0..width).map(|col| Position::new(row, col)).collect())
+                .collect(),
+            Direction::East => (0..height)
+                .map(|row| {
+                    (0..width)
+                        .rev()
+                        .map(|col| Position::new(row, col))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    pub fn tilt(&mut self, direction: Direction) {
+        for lane in self.lanes(direction) {
+            let mut free = 0;
+            for (indx, pos) in lane.iter().enumerate() {
+                match self.grid.get(*pos) {
+                    Some(&CUBE_ROCK) => free = indx + 1,
+                    Some(&ROUND_ROCK) => {
+                        if let Some(cell) = self.grid.get_mut(*pos) {
+                            *cell = EMPTY;
+                        }
+                        if let Some(cell) = self.grid.get_mut(lane[free]) {
+                            *cell = ROUND_ROCK;
+                        }
+                        free += 1;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    pub fn spin(&mut self) {
+        for direction in [
+            Direction::North,
+            Direction::West,
+            Direction::South,
+            Direction::East,
+        ] {
+            self.tilt(direction);
+        }
+    }
+
+    pub fn spin_cycles(&mut self, cycles: usize) {
+        let mut seen: HashMap<Platform, usize> = HashMap::new();
+        let mut cycle = 0;
+        while cycle < cycles {
+            if let Some(first_seen) = seen.insert(self.clone(), cycle) {
+                // the states repeat from here on, skip as many full periods as possible
+                let period = cycle - first_seen;
+                let remaining = (cycles - cycle) % period;
+                for _ in 0..remaining {
+                    self.spin();
+                }
+                return;
+            }
+            self.spin();
+            cycle += 1;
+        }
+    }
+
+    pub fn north_load(&self) -> usize {
+        let height = self.grid.height();
+        (0..height)
+            .flat_map(|row| (0..self.grid.width()).map(move |col| Position::new(row, col)))
+            .filter(|pos| self.grid.get(*pos) == Some(&ROUND_ROCK))
+            .map(|pos| height - pos.row)
+            .sum()
+    }
+}
+
+impl TryFrom<&str> for Platform {
+    type Error = anyhow::Error;
+    fn try_from(content: &str) -> Result<Self, Self::Error> {
+        let grid = Grid::parse(content, |c| match c {
+            ROUND_ROCK | CUBE_ROCK | EMPTY => Ok(c),
+            _ => Err(anyhow!("Unexpected tile {}", c)),
+        })?;
+        Ok(Self { grid })
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+
+    #[test]
+    fn tilt_north() {
+        let mut platform = Platform::try_from(EXAMPLE).unwrap();
+        platform.tilt(Direction::North);
+        let expected = Platform::try_from(
+            "OOOO.#.O..
+OO..#....#
+OO..O##..O
+O..#.OO...
+........#.
+..#....#.#
+..O..#.O.O
+..O.......
+#....###..
+#....#....",
+        )
+        .unwrap();
+        assert_eq!(platform, expected);
+        assert_eq!(platform.north_load(), 136);
+    }
+
+    #[test]
+    fn spin_once() {
+        let mut platform = Platform::try_from(EXAMPLE).unwrap();
+        platform.spin();
+        let expected = Platform::try_from(
+            ".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....",
+        )
+        .unwrap();
+        assert_eq!(platform, expected);
+    }
+
+    #[test]
+    fn spin_three_times() {
+        let mut platform = Platform::try_from(EXAMPLE).unwrap();
+        platform.spin_cycles(3);
+        let expected = Platform::try_from(
+            ".....#....
+....#...O#
+.....##...
+..O#......
+.....OOO#.
+.O#...O#.#
+....O#...O
+.......OOO
+#...O###.O
+#.OOO#...O",
+        )
+        .unwrap();
+        assert_eq!(platform, expected);
+    }
+
+    #[test]
+    fn spin_billion_times() {
+        let mut platform = Platform::try_from(EXAMPLE).unwrap();
+        platform.spin_cycles(1_000_000_000);
+        assert_eq!(platform.north_load(), 64);
+    }
+}
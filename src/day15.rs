@@ -0,0 +1,132 @@
+use anyhow::anyhow;
+
+pub fn part1(content: &str) -> anyhow::Result<u32> {
+    let sum = init_steps(content).map(|step| hash(step) as u32).sum();
+    Ok(sum)
+}
+pub fn part2(content: &str) -> anyhow::Result<usize> {
+    let operations = init_steps(content)
+        .map(Operation::try_from)
+        .collect::<Result<Vec<Operation>, _>>()?;
+    let mut boxes = LensBoxes::new();
+    operations.iter().for_each(|op| boxes.apply(op));
+    Ok(boxes.focusing_power())
+}
+
+fn init_steps(content: &str) -> impl Iterator<Item = &str> {
+    content
+        .split(',')
+        .map(|step| step.trim())
+        .filter(|step| !step.is_empty())
+}
+
+pub fn hash(step: &str) -> u8 {
+    step.bytes()
+        .filter(|c| *c != b'\n')
+        .fold(0_u8, |acc, c| acc.wrapping_add(c).wrapping_mul(17))
+}
+
+#[derive(Debug, PartialEq)]
+enum Operation<'a> {
+    Remove(&'a str),
+    Insert(&'a str, u8),
+}
+
+impl<'a> TryFrom<&'a str> for Operation<'a> {
+    type Error = anyhow::Error;
+    fn try_from(step: &'a str) -> Result<Self, Self::Error> {
+        if let Some(label) = step.strip_suffix('-') {
+            return Ok(Operation::Remove(label));
+        }
+        let (label, focal_length) = step
+            .split_once('=')
+            .ok_or(anyhow!("Could not split {} at '='", step))?;
+        Ok(Operation::Insert(label, focal_length.parse::<u8>()?))
+    }
+}
+
+struct LensBoxes<'a> {
+    boxes: Vec<Vec<(&'a str, u8)>>,
+}
+
+impl<'a> LensBoxes<'a> {
+    pub fn new() -> Self {
+        Self {
+            boxes: vec![vec![]; 256],
+        }
+    }
+
+    pub fn apply(&mut self, operation: &Operation<'a>) {
+        match operation {
+            Operation::Remove(label) => {
+                self.boxes[hash(label) as usize].retain(|(lens, _)| lens != label);
+            }
+            Operation::Insert(label, focal_length) => {
+                let lens_box = &mut self.boxes[hash(label) as usize];
+                match lens_box.iter_mut().find(|(lens, _)| lens == label) {
+                    Some(lens) => lens.1 = *focal_length,
+                    None => lens_box.push((label, *focal_length)),
+                }
+            }
+        }
+    }
+
+    pub fn focusing_power(&self) -> usize {
+        self.boxes
+            .iter()
+            .enumerate()
+            .flat_map(|(box_indx, lens_box)| {
+                lens_box
+                    .iter()
+                    .enumerate()
+                    .map(move |(slot, (_, focal_length))| {
+                        (box_indx + 1) * (slot + 1) * *focal_length as usize
+                    })
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
+    #[test]
+    fn hash_string() {
+        assert_eq!(hash("HASH"), 52);
+        assert_eq!(hash("rn=1"), 30);
+        assert_eq!(hash("rn"), 0);
+        assert_eq!(hash("qp"), 1);
+    }
+
+    #[test]
+    fn hash_example_steps() {
+        let sum = init_steps(EXAMPLE)
+            .map(|step| hash(step) as u32)
+            .sum::<u32>();
+        assert_eq!(sum, 1320);
+    }
+
+    #[test]
+    fn parse_operation() {
+        assert_eq!(Operation::try_from("cm-").unwrap(), Operation::Remove("cm"));
+        assert_eq!(
+            Operation::try_from("ot=9").unwrap(),
+            Operation::Insert("ot", 9)
+        );
+        assert!(Operation::try_from("ot").is_err());
+    }
+
+    #[test]
+    fn focusing_power() {
+        let mut boxes = LensBoxes::new();
+        for step in init_steps(EXAMPLE) {
+            boxes.apply(&Operation::try_from(step).unwrap());
+        }
+        assert_eq!(boxes.boxes[0], [("rn", 1), ("cm", 2)]);
+        assert_eq!(boxes.boxes[3], [("ot", 7), ("ab", 5), ("pc", 6)]);
+        assert_eq!(boxes.focusing_power(), 145);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::grid::{Direction, Grid, Position};
+use anyhow::anyhow;
+use std::collections::HashSet;
+
+pub fn part1(content: &str) -> anyhow::Result<usize> {
+    let contraption = Contraption::try_from(content)?;
+    Ok(contraption.energised(Position::new(0, 0), Direction::East))
+}
+pub fn part2(content: &str) -> anyhow::Result<usize> {
+    let contraption = Contraption::try_from(content)?;
+    Ok(contraption.best_energised())
+}
+
+#[derive(Debug)]
+struct Contraption {
+    grid: Grid<char>,
+}
+
+fn deflect(tile: char, direction: Direction) -> Vec<Direction> {
+    match (tile, direction) {
+        ('/', Direction::East) => vec![Direction::North],
+        ('/', Direction::North) => vec![Direction::East],
+        ('/', Direction::West) => vec![Direction::South],
+        ('/', Direction::South) => vec![Direction::West],
+        ('\\', Direction::East) => vec![Direction::South],
+        ('\\', Direction::South) => vec![Direction::East],
+        ('\\', Direction::West) => vec![Direction::North],
+        ('\\', Direction::North) => vec![Direction::West],
+        ('|', Direction::East | Direction::West) => vec![Direction::North, Direction::South],
+        ('-', Direction::North | Direction::South) => vec![Direction::East, Direction::West],
+        _ => vec![direction],
+    }
+}
+
+impl Contraption {
+    pub fn energised(&self, start: Position, direction: Direction) -> usize {
+        let mut visited: HashSet<(Position, Direction)> = HashSet::new();
+        let mut beams = vec![(start, direction)];
+        while let Some((pos, direction)) = beams.pop() {
+            let Some(tile) = self.grid.get(pos) else {
+                continue;
+            };
+            if !visited.insert((pos, direction)) {
+                continue;
+            }
+            for next_direction in deflect(*tile, direction) {
+                if let Some(next) = self.grid.neighbour(pos, next_direction) {
+                    beams.push((next, next_direction));
+                }
+            }
+        }
+        visited
+            .iter()
+            .map(|(pos, _)| pos)
+            .collect::<HashSet<&Position>>()
+            .len()
+    }
+
+    fn edge_starts(&self) -> Vec<(Position, Direction)> {
+        let (width, height) = (self.grid.width(), self.grid.height());
+        let mut starts = Vec::with_capacity(2 * (width + height));
+        for col in 0..width {
+            starts.push((Position::new(0, col), Direction::South));
+            starts.push((Position::new(height - 1, col), Direction::North));
+        }
+        for row in 0..height {
+            starts.push((Position::new(row, 0), Direction::East));
+            starts.push((Position::new(row, width - 1), Direction::West));
+        }
+        starts
+    }
+
+    pub fn best_energised(&self) -> usize {
+        let starts = self.edge_starts();
+        let workers = std::thread::available_parallelism()
+            .map(|el| el.get())
+            .unwrap_or(1);
+        let chunk_size = starts.len().div_ceil(workers).max(1);
+        std::thread::scope(|scope| {
+            let handles = starts
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|(pos, direction)| self.energised(*pos, *direction))
+                            .max()
+                            .unwrap_or_default()
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    // a worker only panics on a bug, which must not pass for 0 tiles
+                    handle
+                        .join()
+                        .unwrap_or_else(|err| std::panic::resume_unwind(err))
+                })
+                .max()
+                .unwrap_or_default()
+        })
+    }
+}
+
+impl TryFrom<&str> for Contraption {
+    type Error = anyhow::Error;
+    fn try_from(content: &str) -> Result<Self, Self::Error> {
+        let grid = Grid::parse(content, |c| match c {
+            '.' | '/' | '\\' | '|' | '-' => Ok(c),
+            _ => Err(anyhow!("Unexpected tile {}", c)),
+        })?;
+        Ok(Self { grid })
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....";
+
+    #[test]
+    fn deflect_beams() {
+        assert_eq!(deflect('.', Direction::East), [Direction::East]);
+        assert_eq!(deflect('/', Direction::East), [Direction::North]);
+        assert_eq!(deflect('\\', Direction::North), [Direction::West]);
+        assert_eq!(deflect('-', Direction::East), [Direction::East]);
+        assert_eq!(
+            deflect('|', Direction::West),
+            [Direction::North, Direction::South]
+        );
+    }
+
+    #[test]
+    fn energised_from_top_left() {
+        let contraption = Contraption::try_from(EXAMPLE).unwrap();
+        assert_eq!(
+            contraption.energised(Position::new(0, 0), Direction::East),
+            46
+        );
+    }
+
+    #[test]
+    fn best_entry_edge() {
+        let contraption = Contraption::try_from(EXAMPLE).unwrap();
+        assert_eq!(
+            contraption.energised(Position::new(0, 3), Direction::South),
+            51
+        );
+        assert_eq!(contraption.best_energised(), 51);
+    }
+}
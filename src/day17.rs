@@ -0,0 +1,140 @@
+use crate::grid::{Direction, Grid, Position};
+use anyhow::anyhow;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::ops::RangeInclusive;
+
+pub fn part1(content: &str) -> anyhow::Result<u32> {
+    let city = CityMap::try_from(content)?;
+    city.min_heat_loss(0..=3)
+        .ok_or(anyhow!("No path reaches the factory"))
+}
+pub fn part2(content: &str) -> anyhow::Result<u32> {
+    let city = CityMap::try_from(content)?;
+    city.min_heat_loss(4..=10)
+        .ok_or(anyhow!("No path reaches the factory"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct CrucibleState {
+    pos: Position,
+    direction: Direction,
+    run: usize,
+}
+
+#[derive(Debug)]
+struct CityMap {
+    grid: Grid<u32>,
+}
+
+impl CityMap {
+    fn moves(&self, state: &CrucibleState, runs: &RangeInclusive<usize>) -> Vec<CrucibleState> {
+        Direction::ALL
+            .into_iter()
+            .filter(|direction| *direction != state.direction.opposite())
+            .filter_map(|direction| {
+                let run = if direction == state.direction {
+                    if state.run >= *runs.end() {
+                        return None;
+                    }
+                    state.run + 1
+                } else {
+                    if state.run < *runs.start() {
+                        return None;
+                    }
+                    1
+                };
+                let pos = self.grid.neighbour(state.pos, direction)?;
+                Some(CrucibleState {
+                    pos,
+                    direction,
+                    run,
+                })
+            })
+            .collect()
+    }
+
+    pub fn min_heat_loss(&self, runs: RangeInclusive<usize>) -> Option<u32> {
+        // Dijkstra over (position, direction, run length) states; a run length of 0 only
+        // appears at the start, where the crucible may still head either east or south
+        let target = Position::new(self.grid.height() - 1, self.grid.width() - 1);
+        let mut best: HashMap<CrucibleState, u32> = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        for direction in [Direction::East, Direction::South] {
+            let state = CrucibleState {
+                pos: Position::new(0, 0),
+                direction,
+                run: 0,
+            };
+            best.insert(state, 0);
+            queue.push(Reverse((0, state)));
+        }
+        while let Some(Reverse((heat_loss, state))) = queue.pop() {
+            if state.pos == target && state.run >= *runs.start() {
+                return Some(heat_loss);
+            }
+            if best.get(&state).is_some_and(|known| *known < heat_loss) {
+                continue;
+            }
+            for next in self.moves(&state, &runs) {
+                let next_heat_loss = heat_loss + self.grid.get(next.pos).copied().unwrap_or(0);
+                if best.get(&next).is_none_or(|known| next_heat_loss < *known) {
+                    best.insert(next, next_heat_loss);
+                    queue.push(Reverse((next_heat_loss, next)));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl TryFrom<&str> for CityMap {
+    type Error = anyhow::Error;
+    fn try_from(content: &str) -> Result<Self, Self::Error> {
+        let grid = Grid::parse(content, |c| {
+            c.to_digit(10)
+                .ok_or(anyhow!("Unexpected heat loss value {}", c))
+        })?;
+        Ok(Self { grid })
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533";
+
+    #[test]
+    fn crucible() {
+        let city = CityMap::try_from(EXAMPLE).unwrap();
+        assert_eq!(city.min_heat_loss(0..=3), Some(102));
+    }
+
+    #[test]
+    fn ultra_crucible() {
+        let city = CityMap::try_from(EXAMPLE).unwrap();
+        assert_eq!(city.min_heat_loss(4..=10), Some(94));
+        let city = CityMap::try_from(
+            "111111111111
+999999999991
+999999999991
+999999999991
+999999999991",
+        )
+        .unwrap();
+        assert_eq!(city.min_heat_loss(4..=10), Some(71));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::grid::Direction;
+use anyhow::anyhow;
+
+pub fn part1(content: &str) -> anyhow::Result<i64> {
+    let instructions = content
+        .lines()
+        .map(DigInstruction::try_from_line)
+        .collect::<Result<Vec<DigInstruction>, _>>()?;
+    let steps = instructions
+        .iter()
+        .map(|el| (el.direction, el.length))
+        .collect::<Vec<(Direction, i64)>>();
+    lagoon_volume(&steps).ok_or(anyhow!("The lagoon is too large to measure"))
+}
+pub fn part2(content: &str) -> anyhow::Result<i64> {
+    let instructions = content
+        .lines()
+        .map(DigInstruction::try_from_line)
+        .collect::<Result<Vec<DigInstruction>, _>>()?;
+    let steps = instructions
+        .iter()
+        .map(|el| el.decode_color())
+        .collect::<Result<Vec<(Direction, i64)>, _>>()?;
+    lagoon_volume(&steps).ok_or(anyhow!("The lagoon is too large to measure"))
+}
+
+#[derive(Debug, PartialEq)]
+struct DigInstruction {
+    direction: Direction,
+    length: i64,
+    color: String,
+}
+
+impl DigInstruction {
+    pub fn decode_color(&self) -> anyhow::Result<(Direction, i64)> {
+        let hex = self
+            .color
+            .strip_prefix('#')
+            .filter(|hex| hex.len() == 6 && hex.is_ascii())
+            .ok_or(anyhow!("Malformed color {}", self.color))?;
+        let length = i64::from_str_radix(&hex[..5], 16)?;
+        let direction = match &hex[5..] {
+            "0" => Direction::East,
+            "1" => Direction::South,
+            "2" => Direction::West,
+            "3" => Direction::North,
+            other => return Err(anyhow!("Unknown direction digit {}", other)),
+        };
+        Ok((direction, length))
+    }
+
+    fn try_from_line(line: &str) -> anyhow::Result<Self> {
+        let mut parts = line.split_whitespace();
+        let direction = match parts.next() {
+            Some("U") => Direction::North,
+            Some("R") => Direction::East,
+            Some("D") => Direction::South,
+            Some("L") => Direction::West,
+            _ => return Err(anyhow!("Could not read a direction from {}", line)),
+        };
+        let length = parts
+            .next()
+            .ok_or(anyhow!("Could not read a length from {}", line))?
+            .parse::<i64>()?;
+        let color = parts
+            .next()
+            .and_then(|el| el.strip_prefix('('))
+            .and_then(|el| el.strip_suffix(')'))
+            .ok_or(anyhow!("Could not read a color from {}", line))?
+            .to_string();
+        Ok(Self {
+            direction,
+            length,
+            color,
+        })
+    }
+}
+
+fn lagoon_volume(steps: &[(Direction, i64)]) -> Option<i64> {
+    // shoelace formula over the trench corners gives the interior area, Pick's theorem
+    // then adds the half of the boundary cells the polygon cuts through
+    let (mut row, mut col) = (0_i64, 0_i64);
+    let mut double_area = 0_i64;
+    let mut boundary = 0_i64;
+    for (direction, length) in steps {
+        // checked maths, hostile lengths can overflow the corners or the area
+        let (next_row, next_col) = match direction {
+            Direction::North => (row.checked_sub(*length)?, col),
+            Direction::East => (row, col.checked_add(*length)?),
+            Direction::South => (row.checked_add(*length)?, col),
+            Direction::West => (row, col.checked_sub(*length)?),
+        };
+        let cross = col
+            .checked_mul(next_row)?
+            .checked_sub(next_col.checked_mul(row)?)?;
+        double_area = double_area.checked_add(cross)?;
+        boundary = boundary.checked_add(*length)?;
+        (row, col) = (next_row, next_col);
+    }
+    (double_area.checked_abs()? / 2).checked_add(boundary / 2 + 1)
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)";
+
+    #[test]
+    fn parse_line() {
+        let res = DigInstruction::try_from_line("R 6 (#70c710)");
+        assert!(
+            res.is_ok(),
+            "Failed to parse DigInstruction from line: {}",
+            res.unwrap_err()
+        );
+        let instruction = res.unwrap();
+        assert_eq!(instruction.direction, Direction::East);
+        assert_eq!(instruction.length, 6);
+        assert_eq!(
+            instruction.decode_color().unwrap(),
+            (Direction::East, 461937)
+        );
+    }
+
+    #[test]
+    fn lagoon_from_instructions() {
+        let steps = EXAMPLE
+            .lines()
+            .map(|line| DigInstruction::try_from_line(line).unwrap())
+            .map(|el| (el.direction, el.length))
+            .collect::<Vec<(Direction, i64)>>();
+        assert_eq!(lagoon_volume(&steps), Some(62));
+    }
+
+    #[test]
+    fn lagoon_from_colors() {
+        let steps = EXAMPLE
+            .lines()
+            .map(|line| DigInstruction::try_from_line(line).unwrap())
+            .map(|el| el.decode_color().unwrap())
+            .collect::<Vec<(Direction, i64)>>();
+        assert_eq!(lagoon_volume(&steps), Some(952408144115));
+    }
+}
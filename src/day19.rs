@@ -0,0 +1,390 @@
+use anyhow::anyhow;
+use std::collections::{HashMap, HashSet};
+
+pub fn part1(content: &str) -> anyhow::Result<u64> {
+    let (system, parts) = parse_input(content)?;
+    let sum = parts
+        .iter()
+        .filter(|part| system.accepts(part))
+        .map(|part| part.iter().sum::<u64>())
+        .sum();
+    Ok(sum)
+}
+pub fn part2(content: &str) -> anyhow::Result<u64> {
+    let (system, _) = parse_input(content)?;
+    Ok(system.accepted_combinations(1, 4000))
+}
+
+const CATEGORIES: &[char] = &['x', 'm', 'a', 's'];
+const START_WORKFLOW: &str = "in";
+
+type Part = [u64; 4];
+type RatingRange = (u64, u64);
+type PartRanges = [RatingRange; 4];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Target {
+    Accept,
+    Reject,
+    Workflow(String),
+}
+
+impl From<&str> for Target {
+    fn from(str: &str) -> Self {
+        match str {
+            "A" => Target::Accept,
+            "R" => Target::Reject,
+            name => Target::Workflow(name.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Condition {
+    category: usize,
+    less_than: bool,
+    value: u64,
+}
+
+impl Condition {
+    pub fn matches(&self, part: &Part) -> bool {
+        if self.less_than {
+            part[self.category] < self.value
+        } else {
+            part[self.category] > self.value
+        }
+    }
+
+    pub fn split(&self, range: RatingRange) -> (Option<RatingRange>, Option<RatingRange>) {
+        // returns the sub-ranges matching and not matching the condition
+        let (lo, hi) = range;
+        let (matching, rest) = if self.less_than {
+            (
+                (lo, hi.min(self.value.saturating_sub(1))),
+                (lo.max(self.value), hi),
+            )
+        } else {
+            (
+                (lo.max(self.value.saturating_add(1)), hi),
+                (lo, hi.min(self.value)),
+            )
+        };
+        let non_empty = |(a, b): RatingRange| (a <= b).then_some((a, b));
+        (non_empty(matching), non_empty(rest))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    condition: Option<Condition>,
+    target: Target,
+}
+
+impl TryFrom<&str> for Rule {
+    type Error = anyhow::Error;
+    fn try_from(str: &str) -> Result<Self, Self::Error> {
+        let Some((condition_str, target)) = str.split_once(':') else {
+            return Ok(Self {
+                condition: None,
+                target: Target::from(str),
+            });
+        };
+        let mut chars = condition_str.chars();
+        let category_char = chars
+            .next()
+            .ok_or(anyhow!("Empty condition in rule {}", str))?;
+        let category = CATEGORIES
+            .iter()
+            .position(|c| *c == category_char)
+            .ok_or(anyhow!(
+                "Unknown category {} in rule {}",
+                category_char,
+                str
+            ))?;
+        let less_than = match chars.next() {
+            Some('<') => true,
+            Some('>') => false,
+            _ => return Err(anyhow!("Unknown comparison in rule {}", str)),
+        };
+        let value = chars.as_str().parse::<u64>()?;
+        Ok(Self {
+            condition: Some(Condition {
+                category,
+                less_than,
+                value,
+            }),
+            target: Target::from(target),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Workflow {
+    name: String,
+    rules: Vec<Rule>,
+}
+
+impl Workflow {
+    pub fn target(&self, part: &Part) -> Option<&Target> {
+        self.rules
+            .iter()
+            .find(|rule| rule.condition.as_ref().is_none_or(|el| el.matches(part)))
+            .map(|rule| &rule.target)
+    }
+
+    fn try_from_line(line: &str) -> anyhow::Result<Self> {
+        let (name, rules_str) = line
+            .strip_suffix('}')
+            .and_then(|el| el.split_once('{'))
+            .ok_or(anyhow!("Could not split {} at '{{'", line))?;
+        let rules = rules_str
+            .split(',')
+            .map(Rule::try_from)
+            .collect::<Result<Vec<Rule>, _>>()?;
+        Ok(Self {
+            name: name.to_string(),
+            rules,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct WorkflowSystem {
+    workflows: HashMap<String, Workflow>,
+}
+
+impl WorkflowSystem {
+    pub fn accepts(&self, part: &Part) -> bool {
+        let mut current = START_WORKFLOW;
+        loop {
+            match self
+                .workflows
+                .get(current)
+                .and_then(|workflow| workflow.target(part))
+            {
+                Some(Target::Accept) => return true,
+                Some(Target::Workflow(name)) => current = name,
+                Some(Target::Reject) | None => return false,
+            }
+        }
+    }
+
+    pub fn accepted_combinations(&self, min: u64, max: u64) -> u64 {
+        self.count_accepted(START_WORKFLOW, [(min, max); 4])
+    }
+
+    fn count_accepted(&self, name: &str, mut ranges: PartRanges) -> u64 {
+        let Some(workflow) = self.workflows.get(name) else {
+            return 0;
+        };
+        let mut accepted = 0;
+        for rule in workflow.rules.iter() {
+            let Some(condition) = &rule.condition else {
+                return accepted + self.count_target(&rule.target, ranges);
+            };
+            let (matching, rest) = condition.split(ranges[condition.category]);
+            if let Some(range) = matching {
+                let mut matching_ranges = ranges;
+                matching_ranges[condition.category] = range;
+                accepted += self.count_target(&rule.target, matching_ranges);
+            }
+            match rest {
+                Some(range) => ranges[condition.category] = range,
+                None => return accepted,
+            }
+        }
+        accepted
+    }
+
+    fn check_loops<'a>(
+        &'a self,
+        name: &'a str,
+        visiting: &mut HashSet<&'a str>,
+        checked: &mut HashSet<&'a str>,
+    ) -> anyhow::Result<()> {
+        // a workflow sending parts back to itself would never accept or reject them
+        if checked.contains(name) {
+            return Ok(());
+        }
+        if !visiting.insert(name) {
+            return Err(anyhow!("Workflow {} loops back on itself", name));
+        }
+        if let Some(workflow) = self.workflows.get(name) {
+            for rule in workflow.rules.iter() {
+                if let Target::Workflow(next) = &rule.target {
+                    self.check_loops(next, visiting, checked)?;
+                }
+            }
+        }
+        visiting.remove(name);
+        checked.insert(name);
+        Ok(())
+    }
+
+    fn count_target(&self, target: &Target, ranges: PartRanges) -> u64 {
+        match target {
+            Target::Accept => ranges.iter().map(|(lo, hi)| hi - lo + 1).product(),
+            Target::Reject => 0,
+            Target::Workflow(name) => self.count_accepted(name, ranges),
+        }
+    }
+}
+
+impl TryFrom<&str> for WorkflowSystem {
+    type Error = anyhow::Error;
+    fn try_from(content: &str) -> Result<Self, Self::Error> {
+        let workflows = content
+            .lines()
+            .map(Workflow::try_from_line)
+            .map(|el| el.map(|workflow| (workflow.name.clone(), workflow)))
+            .collect::<Result<HashMap<String, Workflow>, _>>()?;
+        if !workflows.contains_key(START_WORKFLOW) {
+            return Err(anyhow!("Missing the {} workflow", START_WORKFLOW));
+        }
+        for workflow in workflows.values() {
+            for rule in workflow.rules.iter() {
+                if let Target::Workflow(name) = &rule.target {
+                    if !workflows.contains_key(name) {
+                        return Err(anyhow!(
+                            "Workflow {} targets unknown workflow {}",
+                            workflow.name,
+                            name
+                        ));
+                    }
+                }
+            }
+        }
+        let system = Self { workflows };
+        let mut checked = HashSet::new();
+        for name in system.workflows.keys() {
+            system.check_loops(name, &mut HashSet::new(), &mut checked)?;
+        }
+        Ok(system)
+    }
+}
+
+fn part_from_line(line: &str) -> anyhow::Result<Part> {
+    let ratings = line
+        .strip_prefix('{')
+        .and_then(|el| el.strip_suffix('}'))
+        .ok_or(anyhow!("Could not read ratings from {}", line))?;
+    let mut part = [0; 4];
+    for rating in ratings.split(',') {
+        let (category, value) = rating
+            .split_once('=')
+            .ok_or(anyhow!("Could not split {} at '='", rating))?;
+        let indx = CATEGORIES
+            .iter()
+            .position(|c| category.len() == 1 && category.starts_with(*c))
+            .ok_or(anyhow!("Unknown category {} in {}", category, line))?;
+        // ratings never exceed 4000, u16 keeps the sums well clear of overflowing
+        part[indx] = u64::from(value.parse::<u16>()?);
+    }
+    Ok(part)
+}
+
+fn parse_input(content: &str) -> anyhow::Result<(WorkflowSystem, Vec<Part>)> {
+    let content = content.replace("\r\n", "\n");
+    let (workflows_str, parts_str) = content
+        .split_once("\n\n")
+        .ok_or(anyhow!("Could not separate workflows from part ratings"))?;
+    let system = WorkflowSystem::try_from(workflows_str)?;
+    let parts = parts_str
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(part_from_line)
+        .collect::<Result<Vec<Part>, _>>()?;
+    Ok((system, parts))
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}";
+
+    #[test]
+    fn parse_workflow() {
+        let res = Workflow::try_from_line("px{a<2006:qkq,m>2090:A,rfg}");
+        assert!(
+            res.is_ok(),
+            "Failed to parse Workflow from line: {}",
+            res.unwrap_err()
+        );
+        let workflow = res.unwrap();
+        assert_eq!(workflow.name, "px");
+        assert_eq!(
+            workflow.rules,
+            [
+                Rule {
+                    condition: Some(Condition {
+                        category: 2,
+                        less_than: true,
+                        value: 2006
+                    }),
+                    target: Target::Workflow("qkq".to_string())
+                },
+                Rule {
+                    condition: Some(Condition {
+                        category: 1,
+                        less_than: false,
+                        value: 2090
+                    }),
+                    target: Target::Accept
+                },
+                Rule {
+                    condition: None,
+                    target: Target::Workflow("rfg".to_string())
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_target() {
+        let res = WorkflowSystem::try_from("in{a<2006:qkq,A}");
+        assert!(res.is_err());
+        assert!(res.unwrap_err().to_string().contains("qkq"));
+    }
+
+    #[test]
+    fn looping_workflows() {
+        let res = WorkflowSystem::try_from("in{a<2006:px,A}\npx{x>10:in,R}");
+        assert!(res.is_err());
+        assert!(WorkflowSystem::try_from("in{a<2006:px,px}\npx{x>10:A,R}").is_ok());
+    }
+
+    #[test]
+    fn accepted_parts() {
+        let (system, parts) = parse_input(EXAMPLE).unwrap();
+        assert_eq!(parts[0], [787, 2655, 1222, 2876]);
+        let sum = parts
+            .iter()
+            .filter(|part| system.accepts(part))
+            .map(|part| part.iter().sum::<u64>())
+            .sum::<u64>();
+        assert_eq!(sum, 19114);
+    }
+
+    #[test]
+    fn accepted_combinations() {
+        let (system, _) = parse_input(EXAMPLE).unwrap();
+        assert_eq!(system.accepted_combinations(1, 4000), 167409079868000);
+    }
+}
@@ -1,26 +1,23 @@
 use anyhow::anyhow;
-use std::path::Path;
 
-pub async fn part1(input_file: &Path) -> anyhow::Result<u32> {
-    let content = tokio::fs::read_to_string(input_file).await?;
+pub fn part1(content: &str) -> anyhow::Result<u64> {
     let (max_r, max_g, max_b) = (12, 13, 14);
     let id_sum = content
         .lines()
         .map(GameOutcome::try_from)
         .filter_map(|el| el.ok())
         .filter(|el| el.is_possibile(max_r, max_g, max_b))
-        .map(|el| el.id)
-        .sum::<u32>();
+        .map(|el| el.id as u64)
+        .sum::<u64>();
     Ok(id_sum)
 }
-pub async fn part2(input_file: &Path) -> anyhow::Result<u32> {
-    let content = tokio::fs::read_to_string(input_file).await?;
+pub fn part2(content: &str) -> anyhow::Result<u64> {
     let power_sum = content
         .lines()
         .map(GameOutcome::try_from)
         .filter_map(|el| el.ok())
-        .map(|el| el.min_disposition().power())
-        .sum::<u32>();
+        .map(|el| el.min_disposition().power() as u64)
+        .sum::<u64>();
     Ok(power_sum)
 }
 
@@ -75,11 +72,12 @@ impl TryFrom<&str> for CubesDisposition {
             })
             .collect::<Result<Vec<CubeColor>, _>>()?
             .iter()
-            .fold((0, 0, 0), |acc, el| match el {
-                CubeColor::Red(n) => (acc.0 + n, acc.1, acc.2),
-                CubeColor::Green(n) => (acc.0, acc.1 + n, acc.2),
-                CubeColor::Blue(n) => (acc.0, acc.1, acc.2 + n),
-            });
+            .try_fold((0_u8, 0_u8, 0_u8), |acc, el| match el {
+                CubeColor::Red(n) => acc.0.checked_add(*n).map(|red| (red, acc.1, acc.2)),
+                CubeColor::Green(n) => acc.1.checked_add(*n).map(|green| (acc.0, green, acc.2)),
+                CubeColor::Blue(n) => acc.2.checked_add(*n).map(|blue| (acc.0, acc.1, blue)),
+            })
+            .ok_or(anyhow!("Too many cubes of one color in {}", str))?;
         Ok(Self::new(
             if red > 0 { Some(red) } else { None },
             if green > 0 { Some(green) } else { None },
@@ -160,6 +158,7 @@ impl TryFrom<&str> for GameOutcome {
 #[cfg(test)]
 mod test {
     use super::*;
+    use proptest::prelude::*;
     #[test]
     fn parse_game_single_disposition() {
         let line = "Game 1: 3 blue, 4 red";
@@ -210,4 +209,59 @@ mod test {
         assert_eq!(cubes.green, Some(2), "checking green cubes");
         assert_eq!(cubes.blue, Some(6), "checking blue cubes");
     }
+
+    #[test]
+    fn parse_disposition_overflow() {
+        let parse_res: Result<CubesDisposition, _> = " 200 red, 100 red".try_into();
+        assert!(parse_res.is_err());
+    }
+
+    fn game_draws() -> impl Strategy<Value = Vec<Vec<(usize, u8)>>> {
+        // every draw shows each color at most once, as in the puzzle input
+        let draw = proptest::sample::subsequence(vec![0_usize, 1, 2], 1..=3)
+            .prop_flat_map(|colors| {
+                let len = colors.len();
+                (Just(colors), proptest::collection::vec(1_u8..=20, len))
+            })
+            .prop_map(|(colors, counts)| colors.into_iter().zip(counts).collect::<Vec<_>>());
+        proptest::collection::vec(draw, 1..6)
+    }
+
+    fn game_line(id: u32, draws: &[Vec<(usize, u8)>]) -> String {
+        let colors = [COLOR_STR.0, COLOR_STR.1, COLOR_STR.2];
+        let draws = draws
+            .iter()
+            .map(|draw| {
+                draw.iter()
+                    .map(|(color, count)| format!("{} {}", count, colors[*color]))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            })
+            .collect::<Vec<String>>()
+            .join("; ");
+        format!("Game {}: {}", id, draws)
+    }
+
+    fn naive_max(draws: &[Vec<(usize, u8)>], color: usize) -> u32 {
+        let mut max = 0;
+        for draw in draws {
+            for (draw_color, count) in draw {
+                if *draw_color == color && *count as u32 > max {
+                    max = *count as u32;
+                }
+            }
+        }
+        max
+    }
+
+    proptest! {
+        #[test]
+        fn game_matches_naive(id in 1_u32..1000, draws in game_draws()) {
+            let game = GameOutcome::try_from(game_line(id, &draws).as_str()).unwrap();
+            prop_assert_eq!(game.id, id);
+            let (r, g, b) = (naive_max(&draws, 0), naive_max(&draws, 1), naive_max(&draws, 2));
+            prop_assert_eq!(game.is_possibile(12, 13, 14), r <= 12 && g <= 13 && b <= 14);
+            prop_assert_eq!(game.min_disposition().power(), r.max(1) * g.max(1) * b.max(1));
+        }
+    }
 }
@@ -0,0 +1,280 @@
+use anyhow::anyhow;
+use std::collections::{HashMap, VecDeque};
+
+pub fn part1(content: &str) -> anyhow::Result<u64> {
+    let mut network = ModuleNetwork::try_from(content)?;
+    network.pulse_product(1000)
+}
+pub fn part2(content: &str) -> anyhow::Result<u64> {
+    let mut network = ModuleNetwork::try_from(content)?;
+    network.first_low_pulse_to("rx")
+}
+
+const BROADCASTER: &str = "broadcaster";
+const MAX_PRESSES: u64 = 1_000_000;
+const MAX_PULSES_PER_PRESS: usize = 100_000;
+
+#[derive(Debug, Clone, PartialEq)]
+enum ModuleKind {
+    Broadcaster,
+    FlipFlop(bool),
+    Conjunction(HashMap<String, bool>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct PulseModule {
+    kind: ModuleKind,
+    outputs: Vec<String>,
+}
+
+impl PulseModule {
+    pub fn receive(&mut self, from: &str, high: bool) -> Option<bool> {
+        match &mut self.kind {
+            ModuleKind::Broadcaster => Some(high),
+            ModuleKind::FlipFlop(_) if high => None,
+            ModuleKind::FlipFlop(on) => {
+                *on = !*on;
+                Some(*on)
+            }
+            ModuleKind::Conjunction(memory) => {
+                memory.insert(from.to_string(), high);
+                Some(!memory.values().all(|el| *el))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Pulse {
+    from: String,
+    to: String,
+    high: bool,
+}
+
+#[derive(Debug)]
+struct ModuleNetwork {
+    modules: HashMap<String, PulseModule>,
+}
+
+impl ModuleNetwork {
+    pub fn press(&mut self) -> anyhow::Result<Vec<Pulse>> {
+        let mut sent = vec![];
+        let mut queue = VecDeque::from([Pulse {
+            from: "button".to_string(),
+            to: BROADCASTER.to_string(),
+            high: false,
+        }]);
+        while let Some(pulse) = queue.pop_front() {
+            if let Some(module) = self.modules.get_mut(&pulse.to) {
+                if let Some(high) = module.receive(&pulse.from, pulse.high) {
+                    queue.extend(module.outputs.iter().map(|output| Pulse {
+                        from: pulse.to.clone(),
+                        to: output.clone(),
+                        high,
+                    }));
+                }
+            }
+            sent.push(pulse);
+            // modules feeding each other can keep a single press going forever
+            if sent.len() > MAX_PULSES_PER_PRESS {
+                return Err(anyhow!(
+                    "A single press sent more than {} pulses",
+                    MAX_PULSES_PER_PRESS
+                ));
+            }
+        }
+        Ok(sent)
+    }
+
+    pub fn pulse_product(&mut self, presses: usize) -> anyhow::Result<u64> {
+        let (mut low, mut high) = (0_u64, 0_u64);
+        for _ in 0..presses {
+            for pulse in self.press()? {
+                if pulse.high {
+                    high += 1;
+                } else {
+                    low += 1;
+                }
+            }
+        }
+        low.checked_mul(high)
+            .ok_or(anyhow!("Pulse product of {} and {} overflows", low, high))
+    }
+
+    pub fn first_low_pulse_to(&mut self, target: &str) -> anyhow::Result<u64> {
+        // the target is fed by a single conjunction, which sends a low pulse only once all
+        // its inputs have sent a high pulse in the same press: every input cycles on its
+        // own period, so the answer is the lcm of those periods
+        let feeders = self
+            .modules
+            .iter()
+            .filter(|(_, module)| module.outputs.iter().any(|el| el == target))
+            .collect::<Vec<(&String, &PulseModule)>>();
+        let [(feeder, feeder_module)] = feeders[..] else {
+            return Err(anyhow!(
+                "Expected a single module feeding {}, found {}",
+                target,
+                feeders.len()
+            ));
+        };
+        let ModuleKind::Conjunction(memory) = &feeder_module.kind else {
+            return Err(anyhow!("{} is not fed by a conjunction", target));
+        };
+        let feeder = feeder.clone();
+        let mut periods: HashMap<String, Option<u64>> =
+            memory.keys().map(|el| (el.clone(), None)).collect();
+        for presses in 1..=MAX_PRESSES {
+            for pulse in self.press()? {
+                if pulse.to == feeder && pulse.high {
+                    if let Some(period) = periods.get_mut(&pulse.from) {
+                        period.get_or_insert(presses);
+                    }
+                }
+            }
+            if periods.values().all(|el| el.is_some()) {
+                return periods
+                    .values()
+                    .flatten()
+                    .try_fold(1, |acc, el| lcm(acc, *el))
+                    .ok_or(anyhow!("The input periods of {} overflow", feeder));
+            }
+        }
+        Err(anyhow!(
+            "No cycle found for the inputs of {} after {} presses",
+            feeder,
+            MAX_PRESSES
+        ))
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(a: u64, b: u64) -> Option<u64> {
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+impl TryFrom<&str> for ModuleNetwork {
+    type Error = anyhow::Error;
+    fn try_from(content: &str) -> Result<Self, Self::Error> {
+        let mut modules = content
+            .lines()
+            .map(|line| {
+                let (name, outputs) = line
+                    .split_once(" -> ")
+                    .ok_or(anyhow!("Could not split {} at ' -> '", line))?;
+                let (name, kind) = if let Some(name) = name.strip_prefix('%') {
+                    (name, ModuleKind::FlipFlop(false))
+                } else if let Some(name) = name.strip_prefix('&') {
+                    (name, ModuleKind::Conjunction(HashMap::new()))
+                } else if name == BROADCASTER {
+                    (name, ModuleKind::Broadcaster)
+                } else {
+                    return Err(anyhow!("Unknown module type in {}", line));
+                };
+                let outputs = outputs.split(',').map(|el| el.trim().to_string()).collect();
+                Ok((name.to_string(), PulseModule { kind, outputs }))
+            })
+            .collect::<anyhow::Result<HashMap<String, PulseModule>>>()?;
+        let connections = modules
+            .iter()
+            .flat_map(|(name, module)| {
+                module
+                    .outputs
+                    .iter()
+                    .map(move |output| (name.clone(), output.clone()))
+            })
+            .collect::<Vec<(String, String)>>();
+        for (input, output) in connections {
+            if let Some(PulseModule {
+                kind: ModuleKind::Conjunction(memory),
+                ..
+            }) = modules.get_mut(&output)
+            {
+                memory.insert(input, false);
+            }
+        }
+        Ok(Self { modules })
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const SIMPLE: &str = "broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a";
+
+    const INTERESTING: &str = "broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output";
+
+    #[test]
+    fn parse_network() {
+        let network = ModuleNetwork::try_from(INTERESTING).unwrap();
+        assert_eq!(network.modules.len(), 5);
+        assert_eq!(
+            network.modules["con"].kind,
+            ModuleKind::Conjunction(HashMap::from([
+                ("a".to_string(), false),
+                ("b".to_string(), false)
+            ]))
+        );
+        assert_eq!(network.modules["a"].outputs, ["inv", "con"]);
+    }
+
+    #[test]
+    fn single_press() {
+        let mut network = ModuleNetwork::try_from(SIMPLE).unwrap();
+        let pulses = network.press().unwrap();
+        assert_eq!(pulses.len(), 12);
+        assert_eq!(pulses.iter().filter(|el| el.high).count(), 4);
+    }
+
+    #[test]
+    fn endless_press() {
+        let mut network = ModuleNetwork::try_from("broadcaster -> a\n&a -> a").unwrap();
+        assert!(network.press().is_err());
+    }
+
+    #[test]
+    fn count_pulses() {
+        let mut network = ModuleNetwork::try_from(SIMPLE).unwrap();
+        assert_eq!(network.pulse_product(1000).unwrap(), 32000000);
+        let mut network = ModuleNetwork::try_from(INTERESTING).unwrap();
+        assert_eq!(network.pulse_product(1000).unwrap(), 11687500);
+    }
+
+    #[test]
+    fn first_low_pulse() {
+        // two 4-bit counters resetting at 11 and 13 presses
+        let content = "broadcaster -> g0f0, g1f0
+%g0f0 -> g0f1, g0c
+%g0f1 -> g0f2, g0c
+%g0f2 -> g0f3
+%g0f3 -> g0c
+&g0c -> g0f0, g0f2, g0i
+&g0i -> hub
+%g1f0 -> g1f1, g1c
+%g1f1 -> g1f2
+%g1f2 -> g1f3, g1c
+%g1f3 -> g1c
+&g1c -> g1f0, g1f1, g1i
+&g1i -> hub
+&hub -> rx";
+        let mut network = ModuleNetwork::try_from(content).unwrap();
+        assert_eq!(network.first_low_pulse_to("rx").unwrap(), 143);
+        let mut network = ModuleNetwork::try_from(SIMPLE).unwrap();
+        assert!(network.first_low_pulse_to("rx").is_err());
+    }
+}
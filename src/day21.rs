@@ -0,0 +1,186 @@
+use crate::grid::{Direction, Grid, Position};
+use anyhow::anyhow;
+use std::collections::{HashMap, VecDeque};
+
+pub fn part1(content: &str) -> anyhow::Result<usize> {
+    part1_with_steps(content, 64)
+}
+pub fn part2(content: &str) -> anyhow::Result<u64> {
+    part2_with_steps(content, 26_501_365)
+}
+pub fn part1_with_steps(content: &str, steps: usize) -> anyhow::Result<usize> {
+    let garden = Garden::try_from(content)?;
+    Ok(garden.reachable_plots(steps))
+}
+pub fn part2_with_steps(content: &str, steps: usize) -> anyhow::Result<u64> {
+    let garden = Garden::try_from(content)?;
+    garden.reachable_plots_infinite(steps)
+}
+
+const ROCK: char = '#';
+const PLOT: char = '.';
+const START: char = 'S';
+
+#[derive(Debug)]
+struct Garden {
+    grid: Grid<char>,
+    start: Position,
+}
+
+impl Garden {
+    pub fn reachable_plots(&self, steps: usize) -> usize {
+        let mut distances: HashMap<Position, usize> = HashMap::from([(self.start, 0)]);
+        let mut queue = VecDeque::from([self.start]);
+        while let Some(pos) = queue.pop_front() {
+            let distance = distances[&pos];
+            if distance == steps {
+                continue;
+            }
+            for direction in Direction::ALL {
+                let Some(next) = self.grid.neighbour(pos, direction) else {
+                    continue;
+                };
+                if self.grid.get(next) != Some(&ROCK) && !distances.contains_key(&next) {
+                    distances.insert(next, distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        // a plot reached in d steps can be reached again every two steps after that
+        distances
+            .values()
+            .filter(|distance| *distance % 2 == steps % 2)
+            .count()
+    }
+
+    fn is_rock_tiled(&self, row: i64, col: i64) -> bool {
+        let (height, width) = (self.grid.height() as i64, self.grid.width() as i64);
+        let pos = Position::new(
+            row.rem_euclid(height) as usize,
+            col.rem_euclid(width) as usize,
+        );
+        self.grid.get(pos) == Some(&ROCK)
+    }
+
+    pub fn reachable_plots_tiled(&self, steps: usize) -> u64 {
+        let start = (self.start.row as i64, self.start.col as i64);
+        let mut distances: HashMap<(i64, i64), usize> = HashMap::from([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
+        while let Some((row, col)) = queue.pop_front() {
+            let distance = distances[&(row, col)];
+            if distance == steps {
+                continue;
+            }
+            for next in [
+                (row - 1, col),
+                (row + 1, col),
+                (row, col - 1),
+                (row, col + 1),
+            ] {
+                if !self.is_rock_tiled(next.0, next.1) && !distances.contains_key(&next) {
+                    distances.insert(next, distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        distances
+            .values()
+            .filter(|distance| *distance % 2 == steps % 2)
+            .count() as u64
+    }
+
+    pub fn reachable_plots_infinite(&self, steps: usize) -> anyhow::Result<u64> {
+        // on a square garden whose start row and column are free the reachable area grows
+        // quadratically every time the steps cover one more garden width, so three samples
+        // are enough to extrapolate to any number of steps
+        let size = self.grid.width();
+        if size != self.grid.height() {
+            return Err(anyhow!("Extrapolation needs a square garden"));
+        }
+        let offset = steps % size;
+        let cycles = (steps / size) as u64;
+        if cycles < 3 {
+            return Ok(self.reachable_plots_tiled(steps));
+        }
+        let samples = [0, 1, 2].map(|k| self.reachable_plots_tiled(offset + k * size));
+        extrapolate_quadratic(samples, cycles)
+            .ok_or(anyhow!("The reachable plots do not grow quadratically"))
+    }
+}
+
+fn extrapolate_quadratic(samples: [u64; 3], x: u64) -> Option<u64> {
+    // Newton forward differences for the quadratic through f(0), f(1) and f(2)
+    let [f0, f1, f2] = samples.map(|el| el as i128);
+    let x = x as i128;
+    let first = f1 - f0;
+    let second = f2 - 2 * f1 + f0;
+    u64::try_from(f0 + x * first + x * (x - 1) / 2 * second).ok()
+}
+
+impl TryFrom<&str> for Garden {
+    type Error = anyhow::Error;
+    fn try_from(content: &str) -> Result<Self, Self::Error> {
+        let grid = Grid::parse(content, |c| match c {
+            ROCK | PLOT | START => Ok(c),
+            _ => Err(anyhow!("Unexpected tile {}", c)),
+        })?;
+        let start = grid
+            .find(|tile| *tile == START)
+            .ok_or(anyhow!("Could not find the starting position"))?;
+        Ok(Self { grid, start })
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........";
+
+    #[test]
+    fn reachable_in_garden() {
+        let garden = Garden::try_from(EXAMPLE).unwrap();
+        assert_eq!(garden.reachable_plots(1), 2);
+        assert_eq!(garden.reachable_plots(2), 4);
+        assert_eq!(garden.reachable_plots(6), 16);
+    }
+
+    #[test]
+    fn reachable_in_tiled_garden() {
+        let garden = Garden::try_from(EXAMPLE).unwrap();
+        assert_eq!(garden.reachable_plots_tiled(6), 16);
+        assert_eq!(garden.reachable_plots_tiled(10), 50);
+        assert_eq!(garden.reachable_plots_tiled(50), 1594);
+        assert_eq!(garden.reachable_plots_tiled(100), 6536);
+    }
+
+    #[test]
+    fn quadratic_extrapolation() {
+        // f(x) = 3x^2 + 2x + 7
+        assert_eq!(extrapolate_quadratic([7, 12, 23], 10), Some(327));
+        assert_eq!(extrapolate_quadratic([20, 10, 1], 10), None);
+    }
+
+    #[test]
+    fn extrapolate_open_garden() {
+        let garden = Garden::try_from(".....\n.#...\n..S..\n...#.\n.....").unwrap();
+        for steps in [17, 42, 63] {
+            assert_eq!(
+                garden.reachable_plots_infinite(steps).unwrap(),
+                garden.reachable_plots_tiled(steps),
+                "{} steps",
+                steps
+            );
+        }
+    }
+}
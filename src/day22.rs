@@ -0,0 +1,202 @@
+use anyhow::anyhow;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+pub fn part1(content: &str) -> anyhow::Result<usize> {
+    let bricks = content
+        .lines()
+        .map(Brick::try_from_line)
+        .collect::<Result<Vec<Brick>, _>>()?;
+    let stack = SettledStack::settle(bricks);
+    Ok(stack.disintegrable())
+}
+pub fn part2(content: &str) -> anyhow::Result<usize> {
+    let bricks = content
+        .lines()
+        .map(Brick::try_from_line)
+        .collect::<Result<Vec<Brick>, _>>()?;
+    let stack = SettledStack::settle(bricks);
+    Ok((0..stack.bricks.len())
+        .map(|indx| stack.chain_reaction(indx))
+        .sum())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Brick {
+    start: [usize; 3],
+    end: [usize; 3],
+}
+
+impl Brick {
+    pub fn new(start: [usize; 3], end: [usize; 3]) -> Self {
+        Self { start, end }
+    }
+
+    fn footprint(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.start[0]..=self.end[0])
+            .flat_map(move |x| (self.start[1]..=self.end[1]).map(move |y| (x, y)))
+    }
+
+    fn height(&self) -> usize {
+        self.end[2] - self.start[2] + 1
+    }
+
+    fn try_from_line(line: &str) -> anyhow::Result<Self> {
+        let (start_str, end_str) = line
+            .split_once('~')
+            .ok_or(anyhow!("Could not split {} at '~'", line))?;
+        let parse_coordinates = |str: &str| -> anyhow::Result<[usize; 3]> {
+            let coordinates = str
+                .split(',')
+                // snapshots stay small, u16 keeps the settling arithmetic from overflowing
+                .map(|el| el.trim().parse::<u16>().map(usize::from))
+                .collect::<Result<Vec<usize>, _>>()?;
+            coordinates
+                .try_into()
+                .map_err(|_| anyhow!("Expected 3 coordinates in {}", line))
+        };
+        let (a, b) = (parse_coordinates(start_str)?, parse_coordinates(end_str)?);
+        if (0..3).filter(|axis| a[*axis] != b[*axis]).count() > 1 {
+            return Err(anyhow!("Brick {} is not a straight line", line));
+        }
+        // normalise so that start holds the lowest coordinate on every axis
+        Ok(Self::new(
+            [a[0].min(b[0]), a[1].min(b[1]), a[2].min(b[2])],
+            [a[0].max(b[0]), a[1].max(b[1]), a[2].max(b[2])],
+        ))
+    }
+}
+
+#[derive(Debug)]
+struct SettledStack {
+    bricks: Vec<Brick>,
+    supports: Vec<HashSet<usize>>,
+    supported_by: Vec<HashSet<usize>>,
+}
+
+impl SettledStack {
+    pub fn settle(mut bricks: Vec<Brick>) -> Self {
+        bricks.sort_by_key(|brick| brick.start[2]);
+        let mut height_map: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+        let mut supports = vec![HashSet::new(); bricks.len()];
+        let mut supported_by = vec![HashSet::new(); bricks.len()];
+        for (indx, brick) in bricks.iter_mut().enumerate() {
+            let below = brick
+                .footprint()
+                .filter_map(|cell| height_map.get(&cell))
+                .copied()
+                .collect::<Vec<(usize, usize)>>();
+            let rest_height = below.iter().map(|(z, _)| *z).max().unwrap_or(0);
+            for (_, support) in below.iter().filter(|(z, _)| *z == rest_height) {
+                supports[*support].insert(indx);
+                supported_by[indx].insert(*support);
+            }
+            let height = brick.height();
+            brick.start[2] = rest_height + 1;
+            brick.end[2] = rest_height + height;
+            let top = (brick.end[2], indx);
+            for cell in brick.footprint() {
+                height_map.insert(cell, top);
+            }
+        }
+        Self {
+            bricks,
+            supports,
+            supported_by,
+        }
+    }
+
+    pub fn disintegrable(&self) -> usize {
+        (0..self.bricks.len())
+            .filter(|indx| {
+                self.supports[*indx]
+                    .iter()
+                    .all(|above| self.supported_by[*above].len() > 1)
+            })
+            .count()
+    }
+
+    pub fn chain_reaction(&self, indx: usize) -> usize {
+        // number of other bricks falling once the given one is disintegrated
+        let mut falling = HashSet::from([indx]);
+        let mut queue = VecDeque::from([indx]);
+        while let Some(current) = queue.pop_front() {
+            for above in self.supports[current].iter() {
+                if !falling.contains(above)
+                    && self.supported_by[*above]
+                        .iter()
+                        .all(|el| falling.contains(el))
+                {
+                    falling.insert(*above);
+                    queue.push_back(*above);
+                }
+            }
+        }
+        falling.len() - 1
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9";
+
+    fn example_stack() -> SettledStack {
+        let bricks = EXAMPLE
+            .lines()
+            .map(|line| Brick::try_from_line(line).unwrap())
+            .collect();
+        SettledStack::settle(bricks)
+    }
+
+    #[test]
+    fn parse_line() {
+        let res = Brick::try_from_line("2,2,2~2,2,1");
+        assert!(
+            res.is_ok(),
+            "Failed to parse Brick from line: {}",
+            res.unwrap_err()
+        );
+        assert_eq!(res.unwrap(), Brick::new([2, 2, 1], [2, 2, 2]));
+        assert!(Brick::try_from_line("0,0,1~2,2,1").is_err());
+        assert!(Brick::try_from_line("0,0,1~0,0,70000").is_err());
+    }
+
+    #[test]
+    fn settle_bricks() {
+        let stack = example_stack();
+        let heights = stack
+            .bricks
+            .iter()
+            .map(|brick| (brick.start[2], brick.end[2]))
+            .collect::<Vec<(usize, usize)>>();
+        assert_eq!(
+            heights,
+            [(1, 1), (2, 2), (2, 2), (3, 3), (3, 3), (4, 4), (5, 6)]
+        );
+        assert_eq!(stack.supports[0], HashSet::from([1, 2]));
+        assert_eq!(stack.supported_by[5], HashSet::from([3, 4]));
+    }
+
+    #[test]
+    fn disintegrable_bricks() {
+        assert_eq!(example_stack().disintegrable(), 5);
+    }
+
+    #[test]
+    fn chain_reactions() {
+        let stack = example_stack();
+        assert_eq!(stack.chain_reaction(0), 6);
+        assert_eq!(stack.chain_reaction(5), 1);
+        let total = (0..stack.bricks.len())
+            .map(|indx| stack.chain_reaction(indx))
+            .sum::<usize>();
+        assert_eq!(total, 7);
+    }
+}
@@ -0,0 +1,234 @@
+use crate::grid::{Direction, Grid, Position};
+use anyhow::anyhow;
+
+pub fn part1(content: &str) -> anyhow::Result<usize> {
+    let trails = TrailMap::try_from(content)?;
+    trails.junction_graph(true)?.longest_path()
+}
+pub fn part2(content: &str) -> anyhow::Result<usize> {
+    let trails = TrailMap::try_from(content)?;
+    trails.junction_graph(false)?.longest_path()
+}
+
+const FOREST: char = '#';
+
+fn slope_direction(tile: char) -> Option<Direction> {
+    match tile {
+        '^' => Some(Direction::North),
+        '>' => Some(Direction::East),
+        'v' => Some(Direction::South),
+        '<' => Some(Direction::West),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+struct TrailMap {
+    grid: Grid<char>,
+    start: Position,
+    end: Position,
+}
+
+impl TrailMap {
+    fn is_open(&self, pos: Position) -> bool {
+        self.grid.get(pos).is_some_and(|tile| *tile != FOREST)
+    }
+
+    fn can_move(&self, pos: Position, direction: Direction, slippery: bool) -> bool {
+        // from a slope the next step must go downhill
+        !slippery
+            || self
+                .grid
+                .get(pos)
+                .and_then(|tile| slope_direction(*tile))
+                .is_none_or(|slope| slope == direction)
+    }
+
+    fn open_neighbours(&self, pos: Position) -> Vec<(Direction, Position)> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(|direction| {
+                self.grid
+                    .neighbour(pos, direction)
+                    .filter(|next| self.is_open(*next))
+                    .map(|next| (direction, next))
+            })
+            .collect()
+    }
+
+    fn junctions(&self) -> Vec<Position> {
+        let mut junctions = vec![self.start];
+        for row in 0..self.grid.height() {
+            for col in 0..self.grid.width() {
+                let pos = Position::new(row, col);
+                if self.is_open(pos) && self.open_neighbours(pos).len() > 2 {
+                    junctions.push(pos);
+                }
+            }
+        }
+        junctions.push(self.end);
+        junctions
+    }
+
+    fn follow_trail(
+        &self,
+        from: Position,
+        direction: Direction,
+        junctions: &[Position],
+        slippery: bool,
+    ) -> Option<(usize, usize)> {
+        // walks a corridor until the next junction, returning its index and the distance
+        if !self.can_move(from, direction, slippery) {
+            return None;
+        }
+        let mut prev = from;
+        let mut pos = self
+            .grid
+            .neighbour(from, direction)
+            .filter(|next| self.is_open(*next))?;
+        let mut length = 1;
+        loop {
+            if let Some(indx) = junctions.iter().position(|el| *el == pos) {
+                return Some((indx, length));
+            }
+            let (next_direction, next) = self
+                .open_neighbours(pos)
+                .into_iter()
+                .find(|(_, next)| *next != prev)?;
+            if !self.can_move(pos, next_direction, slippery) {
+                return None;
+            }
+            prev = pos;
+            pos = next;
+            length += 1;
+        }
+    }
+
+    pub fn junction_graph(&self, slippery: bool) -> anyhow::Result<JunctionGraph> {
+        let junctions = self.junctions();
+        if junctions.len() > 64 {
+            return Err(anyhow!(
+                "{} junctions do not fit in the visited bitmask",
+                junctions.len()
+            ));
+        }
+        let edges = junctions
+            .iter()
+            .map(|junction| {
+                Direction::ALL
+                    .into_iter()
+                    .filter_map(|direction| {
+                        self.follow_trail(*junction, direction, &junctions, slippery)
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(JunctionGraph {
+            edges,
+            start: 0,
+            end: junctions.len() - 1,
+        })
+    }
+}
+
+impl TryFrom<&str> for TrailMap {
+    type Error = anyhow::Error;
+    fn try_from(content: &str) -> Result<Self, Self::Error> {
+        let grid = Grid::parse(content, |c| match c {
+            '#' | '.' | '^' | '>' | 'v' | '<' => Ok(c),
+            _ => Err(anyhow!("Unexpected tile {}", c)),
+        })?;
+        let start = (0..grid.width())
+            .map(|col| Position::new(0, col))
+            .find(|pos| grid.get(*pos) == Some(&'.'))
+            .ok_or(anyhow!("Could not find the start in the first row"))?;
+        let last_row = grid.height() - 1;
+        let end = (0..grid.width())
+            .map(|col| Position::new(last_row, col))
+            .find(|pos| grid.get(*pos) == Some(&'.'))
+            .ok_or(anyhow!("Could not find the end in the last row"))?;
+        Ok(Self { grid, start, end })
+    }
+}
+
+#[derive(Debug)]
+struct JunctionGraph {
+    edges: Vec<Vec<(usize, usize)>>,
+    start: usize,
+    end: usize,
+}
+
+impl JunctionGraph {
+    pub fn longest_path(&self) -> anyhow::Result<usize> {
+        self.longest_from(self.start, 1 << self.start)
+            .ok_or(anyhow!("No path reaches the end"))
+    }
+
+    fn longest_from(&self, node: usize, visited: u64) -> Option<usize> {
+        if node == self.end {
+            return Some(0);
+        }
+        self.edges[node]
+            .iter()
+            .filter(|(next, _)| visited & (1 << next) == 0)
+            .filter_map(|(next, length)| {
+                self.longest_from(*next, visited | (1 << next))
+                    .map(|rest| rest + length)
+            })
+            .max()
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#";
+
+    #[test]
+    fn compress_trails() {
+        let trails = TrailMap::try_from(EXAMPLE).unwrap();
+        assert_eq!(trails.start, Position::new(0, 1));
+        assert_eq!(trails.end, Position::new(22, 21));
+        let graph = trails.junction_graph(false).unwrap();
+        assert_eq!(graph.edges.len(), 9);
+        assert_eq!(graph.edges[graph.start].len(), 1);
+        assert_eq!(graph.edges[graph.start][0].1, 15);
+    }
+
+    #[test]
+    fn longest_slippery_path() {
+        let trails = TrailMap::try_from(EXAMPLE).unwrap();
+        let graph = trails.junction_graph(true).unwrap();
+        assert_eq!(graph.longest_path().unwrap(), 94);
+    }
+
+    #[test]
+    fn longest_dry_path() {
+        let trails = TrailMap::try_from(EXAMPLE).unwrap();
+        let graph = trails.junction_graph(false).unwrap();
+        assert_eq!(graph.longest_path().unwrap(), 154);
+    }
+}
@@ -0,0 +1,315 @@
+use anyhow::anyhow;
+
+pub fn part1(content: &str) -> anyhow::Result<usize> {
+    part1_in_area(content, 200_000_000_000_000, 400_000_000_000_000)
+}
+pub fn part1_in_area(content: &str, min: i128, max: i128) -> anyhow::Result<usize> {
+    let hailstones = content
+        .lines()
+        .map(Hailstone::try_from_line)
+        .collect::<Result<Vec<Hailstone>, _>>()?;
+    crossings_in_area(&hailstones, min, max)
+}
+pub fn part2(content: &str) -> anyhow::Result<i128> {
+    let hailstones = content
+        .lines()
+        .map(Hailstone::try_from_line)
+        .collect::<Result<Vec<Hailstone>, _>>()?;
+    let rock = find_rock(&hailstones)?;
+    rock.pos
+        .iter()
+        .try_fold(0_i128, |acc, el| acc.checked_add(*el))
+        .ok_or(anyhow!("Rock position {:?} overflows", rock.pos))
+}
+
+type Vector = [i128; 3];
+
+// vector maths is checked: hostile inputs can push the exact products past i128
+fn sub(a: Vector, b: Vector) -> Option<Vector> {
+    Some([
+        a[0].checked_sub(b[0])?,
+        a[1].checked_sub(b[1])?,
+        a[2].checked_sub(b[2])?,
+    ])
+}
+
+fn add(a: Vector, b: Vector) -> Option<Vector> {
+    Some([
+        a[0].checked_add(b[0])?,
+        a[1].checked_add(b[1])?,
+        a[2].checked_add(b[2])?,
+    ])
+}
+
+fn scale(a: Vector, factor: i128) -> Option<Vector> {
+    Some([
+        a[0].checked_mul(factor)?,
+        a[1].checked_mul(factor)?,
+        a[2].checked_mul(factor)?,
+    ])
+}
+
+fn cross(a: Vector, b: Vector) -> Option<Vector> {
+    let term = |i: usize, j: usize| a[i].checked_mul(b[j])?.checked_sub(a[j].checked_mul(b[i])?);
+    Some([term(1, 2)?, term(2, 0)?, term(0, 1)?])
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Hailstone {
+    pos: Vector,
+    vel: Vector,
+}
+
+impl Hailstone {
+    pub fn new(pos: Vector, vel: Vector) -> Self {
+        Self { pos, vel }
+    }
+
+    pub fn crosses_in_area(&self, other: &Self, min: i128, max: i128) -> Option<bool> {
+        // solve p1 + t v1 = p2 + s v2 on the xy plane with Cramer's rule, keeping every
+        // quantity as an integer multiple of the determinant to stay exact
+        let det = cross(self.vel, other.vel)?[2];
+        if det == 0 {
+            return Some(false);
+        }
+        let delta = sub(other.pos, self.pos)?;
+        let t_num = cross(delta, other.vel)?[2];
+        let s_num = cross(delta, self.vel)?[2];
+        // flipping signs keeps the comparisons below the right way round
+        let sign = det.signum();
+        if t_num.signum() * sign < 0 || s_num.signum() * sign < 0 {
+            return Some(false);
+        }
+        let (t_num, scaled_det) = (t_num.checked_mul(sign)?, det.checked_mul(sign)?);
+        let in_area = |pos: i128, vel: i128| -> Option<bool> {
+            let scaled = pos
+                .checked_mul(scaled_det)?
+                .checked_add(vel.checked_mul(t_num)?)?;
+            Some(scaled >= min.checked_mul(scaled_det)? && scaled <= max.checked_mul(scaled_det)?)
+        };
+        Some(in_area(self.pos[0], self.vel[0])? && in_area(self.pos[1], self.vel[1])?)
+    }
+
+    fn collides(&self, other: &Hailstone) -> bool {
+        // both meet at some t >= 0 when the position gap closes along the velocity gap:
+        // gap = t * closing, so the two are parallel and point the same way
+        let (Some(gap), Some(closing)) = (sub(self.pos, other.pos), sub(other.vel, self.vel))
+        else {
+            return false;
+        };
+        if closing == [0, 0, 0] {
+            return gap == [0, 0, 0];
+        }
+        let dot = (0..3).try_fold(0_i128, |acc, k| {
+            acc.checked_add(gap[k].checked_mul(closing[k])?)
+        });
+        cross(gap, closing) == Some([0, 0, 0]) && dot.is_some_and(|el| el >= 0)
+    }
+
+    fn try_from_line(line: &str) -> anyhow::Result<Self> {
+        let (pos_str, vel_str) = line
+            .split_once('@')
+            .ok_or(anyhow!("Could not split {} at '@'", line))?;
+        let parse_vector = |str: &str| -> anyhow::Result<Vector> {
+            let values = str
+                .split(',')
+                .map(|el| el.trim().parse::<i128>())
+                .collect::<Result<Vec<i128>, _>>()?;
+            values
+                .try_into()
+                .map_err(|_| anyhow!("Expected 3 values in {}", line))
+        };
+        Ok(Self::new(parse_vector(pos_str)?, parse_vector(vel_str)?))
+    }
+}
+
+fn crossings_in_area(hailstones: &[Hailstone], min: i128, max: i128) -> anyhow::Result<usize> {
+    let mut crossings = 0;
+    for (indx, a) in hailstones.iter().enumerate() {
+        for b in hailstones[indx + 1..].iter() {
+            let crosses = a.crosses_in_area(b, min, max).ok_or(anyhow!(
+                "Hailstones {:?} and {:?} are too far out",
+                a,
+                b
+            ))?;
+            if crosses {
+                crossings += 1;
+            }
+        }
+    }
+    Ok(crossings)
+}
+
+fn collision_time(hailstone: &Hailstone, direction: Vector) -> Option<i128> {
+    // time at which a hailstone meets the line through the origin along direction:
+    // (p + t v) x d = 0, so t = -(p x d) / (v x d) on any non-zero component
+    let pos_cross = cross(hailstone.pos, direction)?;
+    let vel_cross = cross(hailstone.vel, direction)?;
+    let k = (0..3).find(|k| vel_cross[*k] != 0)?;
+    if pos_cross[k].checked_rem(vel_cross[k])? != 0 {
+        return None;
+    }
+    pos_cross[k].checked_div(vel_cross[k])?.checked_neg()
+}
+
+fn find_rock(hailstones: &[Hailstone]) -> anyhow::Result<Hailstone> {
+    // in the frame of the first hailstone the rock line goes through the origin and crosses
+    // every other hailstone path, so it lies in the plane spanned by the origin and each of
+    // those paths: the intersection of two such planes gives its direction
+    let [origin, rest @ ..] = hailstones else {
+        return Err(anyhow!("No hailstones to hit"));
+    };
+    let relative = rest
+        .iter()
+        .map(|el| {
+            Some(Hailstone::new(
+                sub(el.pos, origin.pos)?,
+                sub(el.vel, origin.vel)?,
+            ))
+        })
+        .collect::<Option<Vec<Hailstone>>>()
+        .ok_or(anyhow!("Hailstones are too far apart to compare"))?;
+    for (indx, first) in relative.iter().enumerate() {
+        for (jndx, second) in relative.iter().enumerate().skip(indx + 1) {
+            // pairs whose exact products overflow are skipped, another pair may still fit
+            // a pair only fixes a candidate, it still has to hit every other hailstone
+            if let Some(rock) = rock_through(first, second, &rest[indx], &rest[jndx])
+                .filter(|rock| hailstones.iter().all(|el| rock.collides(el)))
+            {
+                return Ok(rock);
+            }
+        }
+    }
+    Err(anyhow!(
+        "Could not find a rock trajectory hitting every hailstone"
+    ))
+}
+
+fn rock_through(
+    first: &Hailstone,
+    second: &Hailstone,
+    first_abs: &Hailstone,
+    second_abs: &Hailstone,
+) -> Option<Hailstone> {
+    let direction = cross(cross(first.pos, first.vel)?, cross(second.pos, second.vel)?)?;
+    // i128::MIN has no absolute value to take a gcd of
+    if direction.contains(&i128::MIN) {
+        return None;
+    }
+    let divisor = direction.iter().fold(0, |acc, el| gcd(acc, *el));
+    if divisor == 0 {
+        return None;
+    }
+    let direction = direction.map(|el| el / divisor);
+    let t1 = collision_time(first, direction)?;
+    let t2 = collision_time(second, direction)?;
+    let elapsed = t2.checked_sub(t1)?;
+    if elapsed == 0 {
+        return None;
+    }
+    // back to the original frame: the rock hits both hailstones at t1 and t2
+    let hit1 = add(first_abs.pos, scale(first_abs.vel, t1)?)?;
+    let hit2 = add(second_abs.pos, scale(second_abs.vel, t2)?)?;
+    let delta = sub(hit2, hit1)?;
+    if delta.iter().any(|el| el.checked_rem(elapsed) != Some(0)) {
+        return None;
+    }
+    let vel = delta.map(|el| el / elapsed);
+    let pos = sub(hit1, scale(vel, t1)?)?;
+    Some(Hailstone::new(pos, vel))
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+
+    fn example_hailstones() -> Vec<Hailstone> {
+        EXAMPLE
+            .lines()
+            .map(|line| Hailstone::try_from_line(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn parse_line() {
+        let res = Hailstone::try_from_line("20, 19, 15 @  1, -5, -3");
+        assert!(
+            res.is_ok(),
+            "Failed to parse Hailstone from line: {}",
+            res.unwrap_err()
+        );
+        assert_eq!(res.unwrap(), Hailstone::new([20, 19, 15], [1, -5, -3]));
+    }
+
+    #[test]
+    fn crossings_in_test_area() {
+        let hailstones = example_hailstones();
+        assert_eq!(
+            hailstones[0].crosses_in_area(&hailstones[1], 7, 27),
+            Some(true)
+        );
+        assert_eq!(
+            hailstones[0].crosses_in_area(&hailstones[3], 7, 27),
+            Some(false)
+        );
+        assert_eq!(
+            hailstones[1].crosses_in_area(&hailstones[2], 7, 27),
+            Some(false)
+        );
+        assert_eq!(crossings_in_area(&hailstones, 7, 27).unwrap(), 2);
+    }
+
+    #[test]
+    fn rock_trajectory() {
+        let rock = find_rock(&example_hailstones()).unwrap();
+        assert_eq!(rock, Hailstone::new([24, 13, 10], [-3, 1, 2]));
+    }
+
+    #[test]
+    fn rock_collisions() {
+        let rock = Hailstone::new([24, 13, 10], [-3, 1, 2]);
+        assert!(example_hailstones().iter().all(|el| rock.collides(el)));
+        // the paths cross, but the hailstone was there at t = -1
+        assert!(!rock.collides(&Hailstone::new([26, 13, 10], [-1, 1, 2])));
+        let mut hailstones = example_hailstones();
+        hailstones.push(Hailstone::new([0, 0, 0], [1, 1, 1]));
+        assert!(find_rock(&hailstones).is_err());
+    }
+
+    #[test]
+    fn rock_trajectory_at_puzzle_scale() {
+        let rock = Hailstone::new(
+            [
+                287_430_900_705_823,
+                451_620_998_712_421,
+                260_730_677_041_648,
+            ],
+            [-185, -265, 20],
+        );
+        let hailstones = [
+            (612_345_678_901, [-12, 87, 143]),
+            (418_765_432_109, [54, -201, -17]),
+            (798_123_456_789, [-301, 12, 88]),
+            (233_456_789_012, [9, 45, -260]),
+        ]
+        .map(|(t, vel): (i128, Vector)| {
+            let pos = [0, 1, 2].map(|k| rock.pos[k] + t * rock.vel[k] - t * vel[k]);
+            Hailstone::new(pos, vel)
+        });
+        assert_eq!(find_rock(&hailstones).unwrap(), rock);
+    }
+}
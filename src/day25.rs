@@ -0,0 +1,149 @@
+use anyhow::anyhow;
+use std::collections::{HashMap, VecDeque};
+
+pub fn part1(content: &str) -> anyhow::Result<usize> {
+    let wiring = Wiring::try_from(content)?;
+    let (first, second) = wiring.split_by_cut(3)?;
+    Ok(first * second)
+}
+pub fn part2(_content: &str) -> anyhow::Result<&'static str> {
+    Ok("Merry Christmas!")
+}
+
+#[derive(Debug)]
+struct Wiring {
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl Wiring {
+    fn augmenting_path(
+        &self,
+        source: usize,
+        sink: usize,
+        flow: &HashMap<(usize, usize), i32>,
+    ) -> (Option<Vec<usize>>, Vec<bool>) {
+        // BFS on the residual graph, every wire carries one unit in either direction
+        let mut parent = vec![None; self.adjacency.len()];
+        let mut reached = vec![false; self.adjacency.len()];
+        reached[source] = true;
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            if node == sink {
+                let mut path = vec![sink];
+                let mut current = sink;
+                while let Some(prev) = parent[current] {
+                    path.push(prev);
+                    current = prev;
+                }
+                path.reverse();
+                return (Some(path), reached);
+            }
+            for next in self.adjacency[node].iter() {
+                let used = flow.get(&(node, *next)).copied().unwrap_or(0);
+                if !reached[*next] && used < 1 {
+                    reached[*next] = true;
+                    parent[*next] = Some(node);
+                    queue.push_back(*next);
+                }
+            }
+        }
+        (None, reached)
+    }
+
+    pub fn split_by_cut(&self, cut_size: usize) -> anyhow::Result<(usize, usize)> {
+        // the two groups are separated by exactly cut_size wires: any sink on the other side
+        // of the first component has a max flow of cut_size, and the nodes still reachable
+        // from the source once the flow is saturated form its group
+        let source = 0;
+        for sink in 1..self.adjacency.len() {
+            let mut flow: HashMap<(usize, usize), i32> = HashMap::new();
+            let mut paths = 0;
+            loop {
+                let (path, reached) = self.augmenting_path(source, sink, &flow);
+                let Some(path) = path else {
+                    let group = reached.iter().filter(|el| **el).count();
+                    // a single node with cut_size wires is not a split worth reporting
+                    if paths == cut_size && group > 1 && group < self.adjacency.len() - 1 {
+                        return Ok((group, self.adjacency.len() - group));
+                    }
+                    break;
+                };
+                paths += 1;
+                if paths > cut_size {
+                    break;
+                }
+                for edge in path.windows(2) {
+                    *flow.entry((edge[0], edge[1])).or_default() += 1;
+                    *flow.entry((edge[1], edge[0])).or_default() -= 1;
+                }
+            }
+        }
+        Err(anyhow!(
+            "No cut of {} wires splits the components",
+            cut_size
+        ))
+    }
+}
+
+impl TryFrom<&str> for Wiring {
+    type Error = anyhow::Error;
+    fn try_from(content: &str) -> Result<Self, Self::Error> {
+        let mut indices: HashMap<&str, usize> = HashMap::new();
+        let mut adjacency: Vec<Vec<usize>> = vec![];
+        let mut node_indx = |name, adjacency: &mut Vec<Vec<usize>>| {
+            *indices.entry(name).or_insert_with(|| {
+                adjacency.push(vec![]);
+                adjacency.len() - 1
+            })
+        };
+        for line in content.lines() {
+            let (name, connections) = line
+                .split_once(':')
+                .ok_or(anyhow!("Could not split {} at ':'", line))?;
+            let from = node_indx(name.trim(), &mut adjacency);
+            for connection in connections.split_whitespace() {
+                let to = node_indx(connection, &mut adjacency);
+                adjacency[from].push(to);
+                adjacency[to].push(from);
+            }
+        }
+        Ok(Self { adjacency })
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+
+    #[test]
+    fn parse_wiring() {
+        let wiring = Wiring::try_from(EXAMPLE).unwrap();
+        assert_eq!(wiring.adjacency.len(), 15);
+        assert_eq!(
+            wiring.adjacency.iter().map(|el| el.len()).sum::<usize>(),
+            66
+        );
+    }
+
+    #[test]
+    fn three_wire_cut() {
+        let wiring = Wiring::try_from(EXAMPLE).unwrap();
+        let (first, second) = wiring.split_by_cut(3).unwrap();
+        assert_eq!(first * second, 54);
+        assert!(wiring.split_by_cut(2).is_err());
+    }
+}
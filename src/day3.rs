@@ -1,15 +1,11 @@
-use std::path::Path;
-
-pub async fn part1(input_file: &Path) -> anyhow::Result<u32> {
-    let content = tokio::fs::read_to_string(input_file).await?;
+pub fn part1(content: &str) -> anyhow::Result<u64> {
     let lines: Vec<&str> = content.lines().collect();
     let part_symbols = find_symbols(&lines);
     let part_nums = find_part_numbers(&lines, &part_symbols);
-    let sum = part_nums.iter().map(|el| el.num()).sum::<u32>();
+    let sum = part_nums.iter().map(|el| el.num() as u64).sum::<u64>();
     Ok(sum)
 }
-pub async fn part2(input_file: &Path) -> anyhow::Result<u32> {
-    let content = tokio::fs::read_to_string(input_file).await?;
+pub fn part2(content: &str) -> anyhow::Result<u64> {
     let lines: Vec<&str> = content.lines().collect();
     let part_symbols = find_symbols(&lines);
     let part_nums = find_part_numbers(&lines, &part_symbols);
@@ -28,7 +24,7 @@ pub fn find_symbols(lines: &[&str]) -> Vec<char> {
     for line in lines {
         let mut new_symbols = line
             .chars()
-            .filter(|el| !el.is_numeric())
+            .filter(|el| !el.is_ascii_digit())
             .filter(|el| *el != '.')
             .filter(|c| !symbols.iter().any(|known_symbol| known_symbol == c))
             .collect();
@@ -78,7 +74,13 @@ impl PartNumber {
         coordinates
             .into_iter()
             .map(|el| {
-                let num = line[el.start()..=el.end()].parse::<u32>()?;
+                // coordinates count chars, not bytes
+                let num = line
+                    .chars()
+                    .skip(el.start())
+                    .take(el.end() - el.start() + 1)
+                    .collect::<String>()
+                    .parse::<u32>()?;
                 Ok(Self::new(num, el))
             })
             .collect()
@@ -137,7 +139,7 @@ impl PartNumberCoordinates {
             };
             let adjacent_after = indx == self.indx_end + 1;
             return adjacent_after | adjacent_before;
-        } else if (self.line as i16 - line as i16).abs() == 1 {
+        } else if self.line.abs_diff(line) == 1 {
             let min_indx = if self.indx_start > 0 {
                 self.indx_start - 1
             } else {
@@ -153,7 +155,7 @@ impl PartNumberCoordinates {
         let nums: Vec<(usize, char)> = line
             .chars()
             .enumerate()
-            .filter(|(_, c)| c.is_numeric())
+            .filter(|(_, c)| c.is_ascii_digit())
             .collect();
 
         if nums.is_empty() {
@@ -174,18 +176,12 @@ impl PartNumberCoordinates {
     }
 
     pub fn check_against_line(&self, line: &str, part_symbols: &[char]) -> bool {
-        let start = if self.indx_start > 0 {
-            self.indx_start - 1
-        } else {
-            0
-        };
-        let end = if self.indx_end < line.len() - 1 {
-            self.indx_end + 1
-        } else {
-            line.len() - 1
-        };
-        line[start..=end]
-            .chars()
+        let start = self.indx_start.saturating_sub(1);
+        let end = self.indx_end + 1;
+        // lines may be shorter than the one holding the number
+        line.chars()
+            .skip(start)
+            .take(end - start + 1)
             .filter(|c| part_symbols.iter().any(|el| el == c))
             .count()
             != 0
@@ -209,8 +205,8 @@ impl Gear {
         Self { part_nums }
     }
 
-    pub fn ratio(&self) -> u32 {
-        self.part_nums[0].num * self.part_nums[1].num
+    pub fn ratio(&self) -> u64 {
+        self.part_nums[0].num as u64 * self.part_nums[1].num as u64
     }
     pub fn from_line(line: &str, part_numbers: &[PartNumber], line_indx: usize) -> Vec<Gear> {
         let candidate_gears_pos: Vec<usize> = line
@@ -238,6 +234,7 @@ impl Gear {
 #[cfg(test)]
 pub mod test {
     use super::*;
+    use proptest::prelude::*;
     #[test]
     fn coordinates_reader() {
         let str = "467..114..";
@@ -246,6 +243,7 @@ pub mod test {
         assert_eq!(coordinates[0].indx_end, 2);
         assert_eq!(coordinates.len(), 2);
     }
+    #[test]
     fn find_part_numbers_on_line() {
         let line1 = "...*......";
         let line2 = "..35..633.";
@@ -257,4 +255,143 @@ pub mod test {
         assert_eq!(nums[0].num(), 35);
         assert_eq!(nums[1].num(), 633);
     }
+    #[test]
+    fn check_against_short_lines() {
+        let coordinates = PartNumberCoordinates::new(1, 3, 5);
+        assert!(!coordinates.check_against_line("", &['*']));
+        assert!(!coordinates.check_against_line("*", &['*']));
+        assert!(coordinates.check_against_line("..*", &['*']));
+        assert_eq!(part1("é12*\n\n.").unwrap(), 12);
+    }
+    #[test]
+    fn skip_unreadable_lines() {
+        // a number too large for a part number only drops its own line
+        assert_eq!(part1("99999999999*\n7*").unwrap(), 7);
+    }
+
+    struct NaiveNumber {
+        num: u32,
+        row: usize,
+        cols: std::ops::RangeInclusive<usize>,
+    }
+
+    fn naive_numbers(lines: &[&str]) -> Vec<NaiveNumber> {
+        let mut numbers = vec![];
+        for (row, line) in lines.iter().enumerate() {
+            let bytes = line.as_bytes();
+            let mut col = 0;
+            while col < bytes.len() {
+                if !bytes[col].is_ascii_digit() {
+                    col += 1;
+                    continue;
+                }
+                let start = col;
+                while col < bytes.len() && bytes[col].is_ascii_digit() {
+                    col += 1;
+                }
+                numbers.push(NaiveNumber {
+                    num: line[start..col].parse().unwrap(),
+                    row,
+                    cols: start..=col - 1,
+                });
+            }
+        }
+        numbers
+    }
+
+    fn neighbours(lines: &[&str], row: usize, col: usize) -> Vec<(usize, usize, u8)> {
+        // all eight neighbours of a cell that fall inside the schematic
+        let mut cells = vec![];
+        for d_row in -1_i64..=1 {
+            for d_col in -1_i64..=1 {
+                if d_row == 0 && d_col == 0 {
+                    continue;
+                }
+                let (r, c) = (row as i64 + d_row, col as i64 + d_col);
+                if r < 0 || c < 0 || r as usize >= lines.len() {
+                    continue;
+                }
+                if let Some(cell) = lines[r as usize].as_bytes().get(c as usize) {
+                    cells.push((r as usize, c as usize, *cell));
+                }
+            }
+        }
+        cells
+    }
+
+    fn naive_part_sum(lines: &[&str]) -> u64 {
+        naive_numbers(lines)
+            .iter()
+            .filter(|number| {
+                number.cols.clone().any(|col| {
+                    neighbours(lines, number.row, col)
+                        .iter()
+                        .any(|(_, _, cell)| *cell != b'.' && !cell.is_ascii_digit())
+                })
+            })
+            .map(|number| number.num as u64)
+            .sum()
+    }
+
+    fn naive_gear_sum(lines: &[&str]) -> u64 {
+        let numbers = naive_numbers(lines);
+        let mut sum = 0;
+        for (row, line) in lines.iter().enumerate() {
+            for (col, cell) in line.bytes().enumerate() {
+                if cell != b'*' {
+                    continue;
+                }
+                let adjacent = numbers
+                    .iter()
+                    .filter(|number| {
+                        neighbours(lines, row, col)
+                            .iter()
+                            .any(|(r, c, _)| *r == number.row && number.cols.contains(c))
+                    })
+                    .collect::<Vec<&NaiveNumber>>();
+                if adjacent.len() == 2 {
+                    sum += adjacent[0].num as u64 * adjacent[1].num as u64;
+                }
+            }
+        }
+        sum
+    }
+
+    fn schematic() -> impl Strategy<Value = Vec<String>> {
+        let cell = prop_oneof![
+            6 => Just('.'),
+            3 => proptest::char::range('0', '9'),
+            1 => proptest::sample::select(vec!['*', '#', '+', '$']),
+        ];
+        (1_usize..8, 1_usize..10).prop_flat_map(move |(rows, width)| {
+            proptest::collection::vec(
+                proptest::collection::vec(cell.clone(), width)
+                    .prop_map(|cells| cells.into_iter().collect::<String>()),
+                rows,
+            )
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn part_numbers_match_naive(rows in schematic()) {
+            let lines = rows.iter().map(|el| el.as_str()).collect::<Vec<&str>>();
+            let part_nums = find_part_numbers(&lines, &find_symbols(&lines));
+            let sum = part_nums.iter().map(|el| el.num() as u64).sum::<u64>();
+            prop_assert_eq!(sum, naive_part_sum(&lines));
+        }
+
+        #[test]
+        fn gear_ratios_match_naive(rows in schematic()) {
+            let lines = rows.iter().map(|el| el.as_str()).collect::<Vec<&str>>();
+            let part_nums = find_part_numbers(&lines, &find_symbols(&lines));
+            let sum = lines
+                .iter()
+                .enumerate()
+                .flat_map(|(indx, line)| Gear::from_line(line, &part_nums, indx))
+                .map(|el| el.ratio())
+                .sum::<u64>();
+            prop_assert_eq!(sum, naive_gear_sum(&lines));
+        }
+    }
 }
@@ -1,24 +1,23 @@
 use anyhow::anyhow;
-use std::path::Path;
-pub async fn part1(input_file: &Path) -> anyhow::Result<u32> {
-    let content = tokio::fs::read_to_string(input_file).await?;
+use std::collections::HashMap;
+pub fn part1(content: &str) -> anyhow::Result<u32> {
     let lines: Vec<&str> = content.lines().collect();
     let scartch_cards = lines
         .iter()
         .map(|line| ScratchCard::try_from_line(line))
         .collect::<Result<Vec<ScratchCard>, _>>()?;
-    let total_score = scartch_cards.iter().map(|card| card.score()).sum();
-    Ok(total_score)
+    scartch_cards.iter().try_fold(0_u32, |acc, card| {
+        acc.checked_add(card.score()?)
+            .ok_or(anyhow!("The total score overflows"))
+    })
 }
-pub async fn part2(input_file: &Path) -> anyhow::Result<usize> {
-    let content = tokio::fs::read_to_string(input_file).await?;
+pub fn part2(content: &str) -> anyhow::Result<usize> {
     let lines: Vec<&str> = content.lines().collect();
     let scartch_cards = lines
         .iter()
         .map(|line| ScratchCard::try_from_line(line))
         .collect::<Result<Vec<ScratchCard>, _>>()?;
-    let processed_cards = ScratchCard::bulk_process(scartch_cards);
-    Ok(processed_cards.len())
+    ScratchCard::bulk_process(&scartch_cards)
 }
 #[derive(Debug, Clone)]
 struct ScratchCard {
@@ -36,29 +35,44 @@ impl ScratchCard {
         }
     }
 
-    pub fn bulk_process(mut cards: Vec<Self>) -> Vec<Self> {
-        let mut indx = 0;
-        let original_len = cards.len();
-        while indx < cards.len() {
-            let mut new_cards = cards[indx].process_card(&cards[..original_len]);
-            cards.append(&mut new_cards);
-            indx += 1;
+    pub fn bulk_process(cards: &[Self]) -> anyhow::Result<usize> {
+        // copies only go to higher ids, so walking the cards by id settles the count of a
+        // card before it hands out copies; copies go to the first card with the won id
+        let mut positions: HashMap<u16, usize> = HashMap::new();
+        for (indx, card) in cards.iter().enumerate() {
+            positions.entry(card.id).or_insert(indx);
         }
-        cards
-    }
-
-    pub fn process_card(&self, all_cards: &[Self]) -> Vec<Self> {
-        let matching_nums = self.matching_nums();
-        if matching_nums == 0 {
-            return vec![];
+        let mut order = (0..cards.len()).collect::<Vec<usize>>();
+        order.sort_by_key(|indx| cards[*indx].id);
+        let mut copies = vec![1_usize; cards.len()];
+        for indx in order {
+            for id in cards[indx].process_card()? {
+                let Some(won) = positions.get(&id) else {
+                    continue;
+                };
+                copies[*won] = copies[*won]
+                    .checked_add(copies[indx])
+                    .ok_or(anyhow!("The copies of card {} overflow", id))?;
+            }
         }
-        let to_find_id = (1..=matching_nums)
-            .map(|el| el as u16 + self.id)
-            .collect::<Vec<u16>>();
-        to_find_id
+        copies
             .iter()
-            .filter_map(|id| all_cards.iter().find(|el| el.id == *id))
-            .cloned()
+            .try_fold(0_usize, |acc, el| acc.checked_add(*el))
+            .ok_or(anyhow!("The total number of cards overflows"))
+    }
+
+    pub fn process_card(&self) -> anyhow::Result<Vec<u16>> {
+        // ids of the cards this one wins a copy of
+        (1..=self.matching_nums())
+            .map(|el| {
+                u16::try_from(el)
+                    .ok()
+                    .and_then(|el| self.id.checked_add(el))
+                    .ok_or(anyhow!(
+                        "Card {} wins copies past the last card id",
+                        self.id
+                    ))
+            })
             .collect()
     }
 
@@ -69,14 +83,17 @@ impl ScratchCard {
             .count()
     }
 
-    pub fn score(&self) -> u32 {
+    pub fn score(&self) -> anyhow::Result<u32> {
         let matching_nums = self.matching_nums();
 
         if matching_nums == 0 {
-            return 0;
+            return Ok(0);
         }
 
-        2_u32.pow(matching_nums as u32 - 1)
+        u32::try_from(matching_nums - 1)
+            .ok()
+            .and_then(|el| 2_u32.checked_pow(el))
+            .ok_or(anyhow!("Card {} has too many matches to score", self.id))
     }
 
     fn try_from_line(line: &str) -> anyhow::Result<Self> {
@@ -107,6 +124,7 @@ impl ScratchCard {
 #[cfg(test)]
 pub mod test {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn parse_line() {
@@ -128,6 +146,98 @@ pub mod test {
             [83, 86, 6, 31, 17, 9, 48, 53],
             "My numbers"
         );
-        assert_eq!(scratch_card.score(), 8, "Score");
+        assert_eq!(scratch_card.score().unwrap(), 8, "Score");
+    }
+
+    #[test]
+    fn hostile_cards() {
+        // 40 repeated winning numbers used to overflow the score
+        let repeated = format!("Card 1: {}| 7", "7 ".repeat(40));
+        assert!(part1(&repeated).is_err());
+        assert_eq!(part2(&repeated).unwrap(), 1);
+        // the last possible id used to overflow when handing out copies
+        assert!(part2("Card 65535: 1 | 1").is_err());
+        assert_eq!(part2("Card 65535: 1 | 2").unwrap(), 1);
+    }
+
+    #[test]
+    fn copies_are_counted() {
+        // every card copies the next ten, which used to store each copy separately
+        let table = |cards: u16| {
+            (1..=cards)
+                .map(|id| format!("Card {}: 1 2 3 4 5 6 7 8 9 10 | 1 2 3 4 5 6 7 8 9 10", id))
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+        assert!(part2(&table(40)).unwrap() > 1 << 30);
+        assert!(part2(&table(200)).is_err());
+    }
+
+    fn naive_copies(cards: &[(Vec<u8>, Vec<u8>)]) -> usize {
+        // literally hand out copies, one card id at a time
+        let matches = |(winning, mine): &(Vec<u8>, Vec<u8>)| {
+            mine.iter().filter(|num| winning.contains(num)).count()
+        };
+        let mut queue = (0..cards.len()).collect::<Vec<usize>>();
+        let mut total = 0;
+        while let Some(indx) = queue.pop() {
+            total += 1;
+            for copy in indx + 1..=indx + matches(&cards[indx]) {
+                if copy < cards.len() {
+                    queue.push(copy);
+                }
+            }
+        }
+        total
+    }
+
+    fn naive_score(winning: &[u8], mine: &[u8]) -> u32 {
+        let mut score = 0;
+        for num in mine {
+            if winning.contains(num) {
+                score = if score == 0 { 1 } else { score * 2 };
+            }
+        }
+        score
+    }
+
+    fn scratch_cards() -> impl Strategy<Value = Vec<(Vec<u8>, Vec<u8>)>> {
+        let card = (
+            proptest::sample::subsequence((1..30).collect::<Vec<u8>>(), 0..=5),
+            proptest::sample::subsequence((1..30).collect::<Vec<u8>>(), 0..=8),
+        );
+        proptest::collection::vec(card, 1..10)
+    }
+
+    fn card_line(id: usize, winning: &[u8], mine: &[u8]) -> String {
+        let join = |nums: &[u8]| {
+            nums.iter()
+                .map(|el| format!("{:>2}", el))
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+        format!("Card {:>3}: {} | {}", id, join(winning), join(mine))
+    }
+
+    proptest! {
+        #[test]
+        fn score_matches_naive(cards in scratch_cards()) {
+            for (indx, (winning, mine)) in cards.iter().enumerate() {
+                let card = ScratchCard::try_from_line(&card_line(indx + 1, winning, mine)).unwrap();
+                prop_assert_eq!(card.score().unwrap(), naive_score(winning, mine));
+            }
+        }
+
+        #[test]
+        fn copies_match_naive(cards in scratch_cards()) {
+            let parsed = cards
+                .iter()
+                .enumerate()
+                .map(|(indx, (winning, mine))| {
+                    ScratchCard::try_from_line(&card_line(indx + 1, winning, mine)).unwrap()
+                })
+                .collect::<Vec<ScratchCard>>();
+            prop_assert_eq!(ScratchCard::bulk_process(&parsed).unwrap(), naive_copies(&cards));
+        }
     }
 }
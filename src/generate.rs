@@ -0,0 +1,790 @@
+use anyhow::anyhow;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+const SPELLED_DIGITS: &[&str] = &[
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const COLORS: &[&str] = &["red", "green", "blue"];
+const SCHEMATIC_SYMBOLS: &[char] = &['*', '#', '+', '$', '/', '@', '=', '%', '&', '-'];
+const SCHEMATIC_WIDTH: usize = 140;
+const MAZE_JUNK: &[char] = &['.', '.', '.', '|', '-', 'L', 'J', '7', 'F'];
+const CONTRAPTION_TILES: &[char] = &['/', '\\', '|', '-'];
+const CATEGORIES: &[char] = &['x', 'm', 'a', 's'];
+const COUNTER_BITS: usize = 12;
+
+pub fn generate(day: u8, size: usize, seed: u64) -> anyhow::Result<String> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    match day {
+        1 => Ok(calibration_document(&mut rng, size)),
+        2 => Ok(game_records(&mut rng, size)),
+        3 => Ok(engine_schematic(&mut rng, size)),
+        4 => scratch_cards(&mut rng, size),
+        10 => pipe_maze(&mut rng, size),
+        11 => Ok(galaxy_image(&mut rng, size)),
+        12 => Ok(condition_records(&mut rng, size)),
+        13 => Ok(mirror_patterns(&mut rng, size)),
+        14 => Ok(random_grid(&mut rng, size, |rng| {
+            match rng.gen_range(0..20) {
+                0..=3 => 'O',
+                4..=6 => '#',
+                _ => '.',
+            }
+        })),
+        15 => Ok(initialization_sequence(&mut rng, size)),
+        16 => Ok(random_grid(&mut rng, size, |rng| {
+            if rng.gen_bool(0.85) {
+                '.'
+            } else {
+                *CONTRAPTION_TILES.choose(rng).unwrap_or(&'.')
+            }
+        })),
+        17 => heat_loss_map(&mut rng, size),
+        18 => dig_plan(&mut rng, size),
+        19 => workflows(&mut rng, size),
+        20 => module_configuration(&mut rng, size),
+        21 => garden_map(&mut rng, size),
+        22 => brick_snapshot(&mut rng, size),
+        23 => hiking_trails(&mut rng, size),
+        24 => hailstones(&mut rng, size),
+        25 => wiring_diagram(&mut rng, size),
+        _ => Err(anyhow!("No input generator for day {}", day)),
+    }
+}
+
+fn calibration_document(rng: &mut StdRng, lines: usize) -> String {
+    let mut content = String::new();
+    for _ in 0..lines {
+        let chunks = rng.gen_range(1..=8);
+        for _ in 0..chunks {
+            match rng.gen_range(0..3) {
+                0 => content.push(char::from(b'0' + rng.gen_range(1..=9))),
+                1 => content.push_str(SPELLED_DIGITS.choose(rng).unwrap_or(&"one")),
+                _ => {
+                    for _ in 0..rng.gen_range(1..=5) {
+                        content.push(char::from(b'a' + rng.gen_range(0..26)));
+                    }
+                }
+            }
+        }
+        content.push('\n');
+    }
+    content
+}
+
+fn game_records(rng: &mut StdRng, games: usize) -> String {
+    let mut content = String::new();
+    for id in 1..=games {
+        let draws = (0..rng.gen_range(1..=6))
+            .map(|_| {
+                let count = rng.gen_range(1..=COLORS.len());
+                COLORS
+                    .choose_multiple(rng, count)
+                    .map(|color| format!("{} {}", rng.gen_range(1..=20), color))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            })
+            .collect::<Vec<String>>()
+            .join("; ");
+        content.push_str(&format!("Game {}: {}\n", id, draws));
+    }
+    content
+}
+
+fn engine_schematic(rng: &mut StdRng, rows: usize) -> String {
+    let mut content = String::new();
+    for _ in 0..rows {
+        let mut line = String::with_capacity(SCHEMATIC_WIDTH);
+        while line.len() < SCHEMATIC_WIDTH {
+            let room = SCHEMATIC_WIDTH - line.len();
+            match rng.gen_range(0..10) {
+                0..=1 if room > 3 => {
+                    let num = rng.gen_range(1..1000);
+                    line.push_str(&num.to_string());
+                    // numbers on the same line are always separated
+                    line.push('.');
+                }
+                2 => line.push(*SCHEMATIC_SYMBOLS.choose(rng).unwrap_or(&'*')),
+                _ => line.push('.'),
+            }
+        }
+        content.push_str(&line);
+        content.push('\n');
+    }
+    content
+}
+
+fn scratch_cards(rng: &mut StdRng, cards: usize) -> anyhow::Result<String> {
+    if cards > u16::MAX as usize {
+        return Err(anyhow!("At most {} scratch cards fit a card id", u16::MAX));
+    }
+    let id_width = cards.to_string().len();
+    let mut content = String::new();
+    for id in 1..=cards {
+        let mut numbers = (1..100).collect::<Vec<u8>>();
+        numbers.shuffle(rng);
+        let winning = &numbers[..10];
+        // cards never copy a card past the end of the table
+        let max_matches = 10.min(cards - id);
+        let matches = rng.gen_range(0..=max_matches);
+        let mut mine = winning[..matches].to_vec();
+        mine.extend_from_slice(&numbers[10..10 + 25 - matches]);
+        mine.shuffle(rng);
+        let format_numbers = |nums: &[u8]| {
+            nums.iter()
+                .map(|el| format!("{:>2}", el))
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+        content.push_str(&format!(
+            "Card {:>width$}: {} | {}\n",
+            id,
+            format_numbers(winning),
+            format_numbers(&mine),
+            width = id_width
+        ));
+    }
+    Ok(content)
+}
+
+fn check_size(day: u8, size: usize, sizes: RangeInclusive<usize>) -> anyhow::Result<()> {
+    match (sizes.contains(&size), *sizes.end()) {
+        (true, _) => Ok(()),
+        (false, usize::MAX) => Err(anyhow!(
+            "Day {} inputs need a size of at least {}",
+            day,
+            sizes.start()
+        )),
+        (false, max) => Err(anyhow!(
+            "Day {} inputs need a size from {} to {}",
+            day,
+            sizes.start(),
+            max
+        )),
+    }
+}
+
+fn grid_text(rows: &[Vec<char>]) -> String {
+    rows.iter()
+        .map(|row| format!("{}\n", row.iter().collect::<String>()))
+        .collect()
+}
+
+fn random_grid<F>(rng: &mut StdRng, side: usize, mut tile: F) -> String
+where
+    F: FnMut(&mut StdRng) -> char,
+{
+    let rows = (0..side)
+        .map(|_| (0..side).map(|_| tile(rng)).collect())
+        .collect::<Vec<Vec<char>>>();
+    grid_text(&rows)
+}
+
+fn toward(from: usize, to: usize) -> usize {
+    match from.cmp(&to) {
+        std::cmp::Ordering::Less => from + 1,
+        std::cmp::Ordering::Greater => from - 1,
+        std::cmp::Ordering::Equal => from,
+    }
+}
+
+fn pipe_maze(rng: &mut StdRng, side: usize) -> anyhow::Result<String> {
+    check_size(10, side, 4..=usize::MAX)?;
+    // the loop goes up the first column, along the tops of columns of random height, down
+    // the last column and back along the bottom row, enclosing the tiles under the tops
+    let (bottom, last) = (side - 2, side - 2);
+    let mut tops = (0..=last)
+        .map(|_| rng.gen_range(1..bottom))
+        .collect::<Vec<usize>>();
+    tops[last] = tops[last - 1];
+    let mut waypoints = vec![];
+    for (col, top) in tops.iter().enumerate().take(last).skip(1) {
+        waypoints.push((*top, col));
+        waypoints.push((*top, col + 1));
+    }
+    waypoints.extend([(bottom, last), (bottom, 1)]);
+    let mut path = vec![];
+    let mut pos = (bottom, 1);
+    for target in waypoints {
+        while pos != target {
+            path.push(pos);
+            pos = (toward(pos.0, target.0), toward(pos.1, target.1));
+        }
+    }
+    let heading =
+        |from: (usize, usize), to: (usize, usize)| match (to.0.cmp(&from.0), to.1.cmp(&from.1)) {
+            (std::cmp::Ordering::Less, _) => 'N',
+            (std::cmp::Ordering::Greater, _) => 'S',
+            (_, std::cmp::Ordering::Less) => 'W',
+            _ => 'E',
+        };
+    let mut rows = (0..side)
+        .map(|_| {
+            (0..side)
+                .map(|_| *MAZE_JUNK.choose(rng).unwrap_or(&'.'))
+                .collect()
+        })
+        .collect::<Vec<Vec<char>>>();
+    for (indx, pos) in path.iter().enumerate() {
+        let prev = path[(indx + path.len() - 1) % path.len()];
+        let next = path[(indx + 1) % path.len()];
+        let mut ends = [heading(*pos, prev), heading(*pos, next)];
+        ends.sort();
+        rows[pos.0][pos.1] = match ends {
+            ['N', 'S'] => '|',
+            ['E', 'W'] => '-',
+            ['E', 'N'] => 'L',
+            ['N', 'W'] => 'J',
+            ['S', 'W'] => '7',
+            _ => 'F',
+        };
+    }
+    // the start may only connect to its two neighbours on the loop
+    let (row, col) = path[rng.gen_range(0..path.len())];
+    rows[row][col] = 'S';
+    for neighbour in [
+        (row - 1, col),
+        (row + 1, col),
+        (row, col - 1),
+        (row, col + 1),
+    ] {
+        if !path.contains(&neighbour) {
+            rows[neighbour.0][neighbour.1] = '.';
+        }
+    }
+    Ok(grid_text(&rows))
+}
+
+fn galaxy_image(rng: &mut StdRng, side: usize) -> String {
+    // some rows and columns are kept free of galaxies so that the image expands
+    let empty_rows = (0..side).map(|_| rng.gen_bool(0.15)).collect::<Vec<bool>>();
+    let empty_cols = (0..side).map(|_| rng.gen_bool(0.15)).collect::<Vec<bool>>();
+    let rows = (0..side)
+        .map(|row| {
+            (0..side)
+                .map(|col| {
+                    if !empty_rows[row] && !empty_cols[col] && rng.gen_bool(0.08) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect()
+        })
+        .collect::<Vec<Vec<char>>>();
+    grid_text(&rows)
+}
+
+fn condition_records(rng: &mut StdRng, lines: usize) -> String {
+    let mut content = String::new();
+    for _ in 0..lines {
+        let groups = (0..rng.gen_range(1..=5))
+            .map(|_| rng.gen_range(1..=5))
+            .collect::<Vec<usize>>();
+        // unknown springs hide a real arrangement, so every record has at least one
+        let mut springs = ".".repeat(rng.gen_range(0..=2));
+        for (indx, group) in groups.iter().enumerate() {
+            if indx > 0 {
+                springs.push_str(&".".repeat(rng.gen_range(1..=2)));
+            }
+            springs.push_str(&"#".repeat(*group));
+        }
+        springs.push_str(&".".repeat(rng.gen_range(0..=2)));
+        let springs = springs
+            .chars()
+            .map(|c| if rng.gen_bool(0.4) { '?' } else { c })
+            .collect::<String>();
+        let groups = groups
+            .iter()
+            .map(|el| el.to_string())
+            .collect::<Vec<String>>()
+            .join(",");
+        content.push_str(&format!("{} {}\n", springs, groups));
+    }
+    content
+}
+
+fn mirror_patterns(rng: &mut StdRng, patterns: usize) -> String {
+    let mut blocks = vec![];
+    for _ in 0..patterns {
+        let (width, height) = (rng.gen_range(5..=15), rng.gen_range(5..=15));
+        let mut rows = (0..height)
+            .map(|_| {
+                (0..width)
+                    .map(|_| if rng.gen_bool(0.5) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect::<Vec<Vec<char>>>();
+        // mirroring the columns and then the rows makes both reflections exact, flipping a
+        // cell right of the mirrored columns then only smudges the row reflection
+        let cols = rng.gen_range(1..=(width - 1) / 2);
+        for row in rows.iter_mut() {
+            for offset in 0..cols {
+                row[cols + offset] = row[cols - 1 - offset];
+            }
+        }
+        let above = rng.gen_range(1..height);
+        let reach = above.min(height - above);
+        for offset in 0..reach {
+            rows[above + offset] = rows[above - 1 - offset].clone();
+        }
+        let smudge_row = rng.gen_range(above - reach..above + reach);
+        let smudge_col = rng.gen_range(2 * cols..width);
+        let smudge = &mut rows[smudge_row][smudge_col];
+        *smudge = if *smudge == '#' { '.' } else { '#' };
+        if rng.gen_bool(0.5) {
+            rows = (0..width)
+                .map(|col| rows.iter().map(|row| row[col]).collect())
+                .collect();
+        }
+        blocks.push(grid_text(&rows));
+    }
+    blocks.join("\n")
+}
+
+fn initialization_sequence(rng: &mut StdRng, steps: usize) -> String {
+    // a small pool of labels makes later steps replace and remove earlier lenses
+    let labels = (0..steps / 4 + 1)
+        .map(|_| {
+            (0..rng.gen_range(2..=4))
+                .map(|_| char::from(b'a' + rng.gen_range(0..26)))
+                .collect()
+        })
+        .collect::<Vec<String>>();
+    let sequence = (0..steps)
+        .map(|_| {
+            let label = labels.choose(rng).map(String::as_str).unwrap_or("a");
+            if rng.gen_bool(0.7) {
+                format!("{}={}", label, rng.gen_range(1..=9))
+            } else {
+                format!("{}-", label)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+    format!("{}\n", sequence)
+}
+
+fn heat_loss_map(rng: &mut StdRng, side: usize) -> anyhow::Result<String> {
+    // the ultra crucible moves four blocks before it may stop at the corner
+    check_size(17, side, 5..=usize::MAX)?;
+    Ok(random_grid(rng, side, |rng| {
+        char::from(b'0' + rng.gen_range(1..=9))
+    }))
+}
+
+fn skyline(
+    rng: &mut StdRng,
+    columns: usize,
+    max_height: usize,
+    max_width: usize,
+) -> Vec<(char, usize)> {
+    // traced from the bottom left corner: up, right across the top of every column with a
+    // step up or down between them, then down the last column and left along the bottom
+    let mut heights: Vec<usize> = vec![];
+    while heights.len() < columns {
+        let height = rng.gen_range(1..=max_height);
+        if heights.last() != Some(&height) {
+            heights.push(height);
+        }
+    }
+    let widths = (0..columns)
+        .map(|_| rng.gen_range(1..=max_width))
+        .collect::<Vec<usize>>();
+    let mut steps = vec![('U', heights[0])];
+    for col in 0..columns {
+        if col > 0 {
+            let (before, after) = (heights[col - 1], heights[col]);
+            steps.push(if after > before {
+                ('U', after - before)
+            } else {
+                ('D', before - after)
+            });
+        }
+        steps.push(('R', widths[col]));
+    }
+    steps.push(('D', heights[columns - 1]));
+    steps.push(('L', widths.iter().sum()));
+    steps
+}
+
+fn dig_plan(rng: &mut StdRng, columns: usize) -> anyhow::Result<String> {
+    // the colors hold five hex digits, so the widths of the second lagoon add up to at most
+    // that and both lagoons need the same number of instructions
+    check_size(18, columns, 1..=0xfffff)?;
+    let plain = skyline(rng, columns, 10, 10);
+    let colors = skyline(rng, columns, 0xfffff, 0xfffff / columns);
+    Ok(plain
+        .iter()
+        .zip(colors.iter())
+        .map(|((direction, length), (color_direction, color_length))| {
+            let digit = match color_direction {
+                'R' => 0,
+                'D' => 1,
+                'L' => 2,
+                _ => 3,
+            };
+            format!(
+                "{} {} (#{:05x}{})\n",
+                direction, length, color_length, digit
+            )
+        })
+        .collect())
+}
+
+fn workflow_name(indx: usize) -> String {
+    // letters after a prefix never collide with "in", "A" or "R"
+    if indx == 0 {
+        return "in".to_string();
+    }
+    let mut name = String::new();
+    let mut rest = indx;
+    while rest > 0 {
+        name.insert(0, char::from(b'a' + (rest % 26) as u8));
+        rest /= 26;
+    }
+    format!("w{}", name)
+}
+
+fn workflows(rng: &mut StdRng, size: usize) -> anyhow::Result<String> {
+    check_size(19, size, 1..=usize::MAX)?;
+    // parts are only sent on to later workflows, so no workflow loops back on itself
+    let target = |rng: &mut StdRng, indx: usize| match rng.gen_range(0..3) {
+        0 => "A".to_string(),
+        1 if indx + 1 < size => workflow_name(rng.gen_range(indx + 1..size)),
+        _ => "R".to_string(),
+    };
+    let mut content = String::new();
+    for indx in 0..size {
+        let rules = (0..rng.gen_range(1..=3))
+            .map(|_| {
+                format!(
+                    "{}{}{}:{}",
+                    CATEGORIES.choose(rng).unwrap_or(&'x'),
+                    if rng.gen_bool(0.5) { '<' } else { '>' },
+                    rng.gen_range(1..4000),
+                    target(rng, indx)
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+        let fallback = target(rng, indx);
+        content.push_str(&format!(
+            "{}{{{},{}}}\n",
+            workflow_name(indx),
+            rules,
+            fallback
+        ));
+    }
+    content.push('\n');
+    for _ in 0..size {
+        let ratings = CATEGORIES
+            .iter()
+            .map(|c| format!("{}={}", c, rng.gen_range(1..=4000)))
+            .collect::<Vec<String>>()
+            .join(",");
+        content.push_str(&format!("{{{}}}\n", ratings));
+    }
+    Ok(content)
+}
+
+fn module_configuration(rng: &mut StdRng, counters: usize) -> anyhow::Result<String> {
+    // as in the puzzle every counter is a chain of flip-flops whose set bits feed a
+    // conjunction, which resets the chain after an odd period of presses; rx waits for all
+    // of them at once and the lcm of more than five 12 bit periods could overflow
+    check_size(20, counters, 1..=5)?;
+    let names = (0..counters)
+        .map(|indx| char::from(b'a' + indx as u8))
+        .collect::<Vec<char>>();
+    let mut lines = vec![];
+    for name in names.iter() {
+        let period = rng.gen_range(1 << (COUNTER_BITS - 1)..1 << COUNTER_BITS) | 1;
+        let mut resets = vec![];
+        for bit in 0..COUNTER_BITS {
+            let mut outputs = vec![];
+            if bit + 1 < COUNTER_BITS {
+                outputs.push(format!("{}{}", name, bit + 1));
+            }
+            if period & (1 << bit) == 0 || bit == 0 {
+                resets.push(format!("{}{}", name, bit));
+            }
+            if period & (1 << bit) != 0 {
+                outputs.push(format!("c{}", name));
+            }
+            lines.push(format!("%{}{} -> {}", name, bit, outputs.join(", ")));
+        }
+        resets.push(format!("i{}", name));
+        lines.push(format!("&c{} -> {}", name, resets.join(", ")));
+        lines.push(format!("&i{} -> hub", name));
+    }
+    lines.push("&hub -> rx".to_string());
+    let starts = names
+        .iter()
+        .map(|name| format!("{}0", name))
+        .collect::<Vec<String>>();
+    lines.push(format!("broadcaster -> {}", starts.join(", ")));
+    lines.shuffle(rng);
+    Ok(lines.iter().map(|line| format!("{}\n", line)).collect())
+}
+
+// `is_multiple_of` needs a newer toolchain than the crate asks for
+#[allow(clippy::manual_is_multiple_of)]
+fn garden_map(rng: &mut StdRng, side: usize) -> anyhow::Result<String> {
+    // extrapolating the infinite garden needs an odd square with the start in the middle
+    // and a free border, middle row and middle column, like the puzzle input
+    if side < 5 || side % 2 == 0 {
+        return Err(anyhow!("Day 21 inputs need an odd size of at least 5"));
+    }
+    let middle = side / 2;
+    let rows = (0..side)
+        .map(|row| {
+            (0..side)
+                .map(|col| {
+                    let free = [0, middle, side - 1];
+                    if (row, col) == (middle, middle) {
+                        'S'
+                    } else if free.contains(&row) || free.contains(&col) || rng.gen_bool(0.9) {
+                        '.'
+                    } else {
+                        '#'
+                    }
+                })
+                .collect()
+        })
+        .collect::<Vec<Vec<char>>>();
+    Ok(grid_text(&rows))
+}
+
+fn brick_snapshot(rng: &mut StdRng, bricks: usize) -> anyhow::Result<String> {
+    // every brick starts on its own four layers so that none overlap before settling, and
+    // the highest one still fits the u16 coordinates
+    check_size(22, bricks, 0..=u16::MAX as usize / 4)?;
+    let mut lines = (0..bricks)
+        .map(|indx| {
+            let length = rng.gen_range(1..=4);
+            let axis = rng.gen_range(0..3);
+            let mut start = [rng.gen_range(0..10), rng.gen_range(0..10), 1 + 4 * indx];
+            if axis < 2 {
+                start[axis] = rng.gen_range(0..=10 - length);
+            }
+            let mut end = start;
+            end[axis] += length - 1;
+            format!(
+                "{},{},{}~{},{},{}\n",
+                start[0], start[1], start[2], end[0], end[1], end[2]
+            )
+        })
+        .collect::<Vec<String>>();
+    lines.shuffle(rng);
+    Ok(lines.concat())
+}
+
+fn hiking_trails(rng: &mut StdRng, junctions: usize) -> anyhow::Result<String> {
+    // a square of junctions joined by corridors, each sloping down or right at both ends;
+    // the longest hike tries every route, so the square stays small
+    check_size(23, junctions, 2..=6)?;
+    let mut offsets = || {
+        let mut at = vec![1];
+        while at.len() < junctions {
+            at.push(at[at.len() - 1] + rng.gen_range(2..=5));
+        }
+        at
+    };
+    let (rows_at, cols_at) = (offsets(), offsets());
+    let (last_row, last_col) = (rows_at[junctions - 1], cols_at[junctions - 1]);
+    let mut rows = vec![vec!['#'; last_col + 2]; last_row + 2];
+    for row in rows_at.iter() {
+        rows[*row][1..=last_col].fill('.');
+        for pair in cols_at.windows(2) {
+            rows[*row][pair[0] + 1] = '>';
+            rows[*row][pair[1] - 1] = '>';
+        }
+    }
+    for col in cols_at.iter() {
+        for row in rows[1..=last_row].iter_mut() {
+            row[*col] = '.';
+        }
+        for pair in rows_at.windows(2) {
+            rows[pair[0] + 1][*col] = 'v';
+            rows[pair[1] - 1][*col] = 'v';
+        }
+    }
+    rows[0][1] = '.';
+    rows[last_row + 1][last_col] = '.';
+    Ok(grid_text(&rows))
+}
+
+fn hailstones(rng: &mut StdRng, count: usize) -> anyhow::Result<String> {
+    // every hailstone starts where a hidden rock meets it at its own time
+    check_size(24, count, 3..=usize::MAX)?;
+    let rock_pos: [i64; 3] =
+        std::array::from_fn(|_| rng.gen_range(100_000_000_000_000..300_000_000_000_000));
+    let rock_vel: [i64; 3] = std::array::from_fn(|_| rng.gen_range(-300..=300));
+    let mut times = HashSet::new();
+    let mut content = String::new();
+    while times.len() < count {
+        let time = rng.gen_range(1_000_000_000..1_000_000_000_000_i64);
+        let vel: [i64; 3] = std::array::from_fn(|_| rng.gen_range(-300..=300));
+        if vel == rock_vel || !times.insert(time) {
+            continue;
+        }
+        let pos: [i64; 3] = std::array::from_fn(|k| rock_pos[k] + time * (rock_vel[k] - vel[k]));
+        content.push_str(&format!(
+            "{}, {}, {} @ {}, {}, {}\n",
+            pos[0], pos[1], pos[2], vel[0], vel[1], vel[2]
+        ));
+    }
+    Ok(content)
+}
+
+fn wiring_diagram(rng: &mut StdRng, components: usize) -> anyhow::Result<String> {
+    // each group wires every component to the next two around a ring, which takes four
+    // cuts to split, so only the three wires between the groups form a cut of three
+    check_size(25, components, 10..=26 * 26 * 26)?;
+    let mut names = (0..26 * 26 * 26)
+        .map(|indx: usize| {
+            [indx / 676, indx / 26 % 26, indx % 26]
+                .iter()
+                .map(|el| char::from(b'a' + *el as u8))
+                .collect()
+        })
+        .collect::<Vec<String>>();
+    names.shuffle(rng);
+    let first = rng.gen_range(5..=components - 5);
+    let mut wires = vec![];
+    for (start, len) in [(0, first), (first, components - first)] {
+        for indx in 0..len {
+            for step in [1, 2] {
+                wires.push((start + indx, start + (indx + step) % len));
+            }
+        }
+    }
+    let left = rand::seq::index::sample(rng, first, 3);
+    let right = rand::seq::index::sample(rng, components - first, 3);
+    wires.extend(left.iter().zip(right.iter()).map(|(a, b)| (a, first + b)));
+    let mut listed = vec![vec![]; components];
+    for (a, b) in wires {
+        let (from, to) = if rng.gen_bool(0.5) { (a, b) } else { (b, a) };
+        listed[from].push(names[to].as_str());
+    }
+    let mut lines = listed
+        .iter()
+        .enumerate()
+        .filter(|(_, to)| !to.is_empty())
+        .map(|(indx, to)| format!("{}: {}\n", names[indx], to.join(" ")))
+        .collect::<Vec<String>>();
+    lines.shuffle(rng);
+    Ok(lines.concat())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn small_size(day: u8) -> usize {
+        match day {
+            20 | 23 => 3,
+            21 => 11,
+            24 => 5,
+            _ => 12,
+        }
+    }
+
+    #[test]
+    fn reproducible_inputs() {
+        for day in [1, 2, 3, 4] {
+            let first = generate(day, 50, 42).unwrap();
+            assert_eq!(first.lines().count(), 50, "day {}", day);
+        }
+        for day in crate::DAYS.iter().copied() {
+            let size = small_size(day);
+            let first = generate(day, size, 42).unwrap();
+            assert_eq!(first, generate(day, size, 42).unwrap(), "day {}", day);
+            assert_ne!(first, generate(day, size, 43).unwrap(), "day {}", day);
+        }
+        assert!(generate(5, 10, 0).is_err());
+        assert!(generate(20, 6, 0).is_err());
+        assert!(generate(21, 12, 0).is_err());
+        assert!(generate(23, 1, 0).is_err());
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn generated_inputs_solve() {
+        let dir = std::env::temp_dir().join(format!("aoc2023-gen-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for day in crate::DAYS.iter().copied() {
+            let path = dir.join(format!("day{day}.txt"));
+            std::fs::write(&path, generate(day, small_size(day), 7).unwrap()).unwrap();
+            for part in [1, 2] {
+                let answer = crate::solve(day, part, &path).await;
+                assert!(
+                    answer.is_ok(),
+                    "day {} part {} failed: {}",
+                    day,
+                    part,
+                    answer.unwrap_err()
+                );
+            }
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn expected_copies(cards: &str) -> Option<usize> {
+        // every card adds its own copies to the next ones, counted wide enough to see overflow
+        let matches = cards
+            .lines()
+            .map(|line| {
+                let (winning, mine) = line.split_once(':').unwrap().1.split_once('|').unwrap();
+                let winning = winning.split_whitespace().collect::<Vec<&str>>();
+                mine.split_whitespace()
+                    .filter(|el| winning.contains(el))
+                    .count()
+            })
+            .collect::<Vec<usize>>();
+        let mut copies = vec![1_u128; matches.len()];
+        for indx in 0..matches.len() {
+            for won in indx + 1..=indx + matches[indx] {
+                copies[won] = copies[won].checked_add(copies[indx])?;
+            }
+        }
+        let total = copies
+            .iter()
+            .try_fold(0_u128, |acc, el| acc.checked_add(*el))?;
+        usize::try_from(total).ok()
+    }
+
+    #[test]
+    fn scratch_card_copies() {
+        // `gen 4 --size 1000` doubles the copies down the table, part two has to either count
+        // them exactly or report the overflow
+        for size in [30, 1000] {
+            let cards = generate(4, size, 0).unwrap();
+            match expected_copies(&cards) {
+                Some(total) => assert_eq!(crate::day4::part2(&cards).unwrap(), total),
+                None => assert!(crate::day4::part2(&cards).is_err()),
+            }
+        }
+    }
+
+    #[test]
+    fn module_counter_periods() {
+        // a single counter sends its low pulse to rx after exactly its period
+        let config = generate(20, 1, 3).unwrap();
+        let period = config
+            .lines()
+            .filter(|line| line.starts_with("%a") && line.contains("ca"))
+            .map(|line| line[2..line.find(' ').unwrap()].parse::<u32>().unwrap())
+            .map(|bit| 1_u64 << bit)
+            .sum::<u64>();
+        assert_eq!(crate::day20::part2(&config).unwrap(), period);
+    }
+
+    #[test]
+    fn schematic_line_width() {
+        let schematic = generate(3, 20, 1).unwrap();
+        assert!(schematic.lines().all(|line| line.len() == SCHEMATIC_WIDTH));
+    }
+}
@@ -0,0 +1,181 @@
+use anyhow::anyhow;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn opposite(&self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Position {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Position {
+    pub fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn parse<F>(content: &str, to_cell: F) -> anyhow::Result<Self>
+    where
+        F: Fn(char) -> anyhow::Result<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(content.len());
+        for line in content.lines() {
+            let line_width = line.chars().count();
+            match width {
+                None => width = Some(line_width),
+                Some(w) if w != line_width => {
+                    return Err(anyhow!(
+                        "Line {} has {} cells, expected {}",
+                        height,
+                        line_width,
+                        w
+                    ))
+                }
+                _ => {}
+            }
+            for c in line.chars() {
+                cells.push(to_cell(c)?);
+            }
+            height += 1;
+        }
+        let width = width
+            .filter(|el| *el > 0)
+            .ok_or(anyhow!("Cannot build a grid from an empty input"))?;
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, pos: Position) -> Option<&T> {
+        if pos.row >= self.height || pos.col >= self.width {
+            return None;
+        }
+        self.cells.get(pos.row * self.width + pos.col)
+    }
+
+    pub fn get_mut(&mut self, pos: Position) -> Option<&mut T> {
+        if pos.row >= self.height || pos.col >= self.width {
+            return None;
+        }
+        self.cells.get_mut(pos.row * self.width + pos.col)
+    }
+
+    pub fn neighbour(&self, pos: Position, direction: Direction) -> Option<Position> {
+        let (row, col) = match direction {
+            Direction::North => (pos.row.checked_sub(1)?, pos.col),
+            Direction::East => (pos.row, pos.col + 1),
+            Direction::South => (pos.row + 1, pos.col),
+            Direction::West => (pos.row, pos.col.checked_sub(1)?),
+        };
+        if row >= self.height || col >= self.width {
+            return None;
+        }
+        Some(Position::new(row, col))
+    }
+
+    pub fn find<P>(&self, predicate: P) -> Option<Position>
+    where
+        P: Fn(&T) -> bool,
+    {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|indx| Position::new(indx / self.width, indx % self.width))
+    }
+}
+
+impl TryFrom<&str> for Grid<char> {
+    type Error = anyhow::Error;
+    fn try_from(content: &str) -> Result<Self, Self::Error> {
+        Self::parse(content, Ok)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_grid() {
+        let parse_res = Grid::try_from("ab\ncd\nef");
+        assert!(
+            parse_res.is_ok(),
+            "parsing error: {}",
+            parse_res.unwrap_err()
+        );
+        let grid = parse_res.unwrap();
+        assert_eq!(grid.width(), 2);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid.get(Position::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.get(Position::new(0, 2)), None);
+        assert_eq!(grid.find(|c| *c == 'd'), Some(Position::new(1, 1)));
+    }
+
+    #[test]
+    fn parse_ragged_grid() {
+        assert!(Grid::try_from("abc\nde").is_err());
+    }
+
+    #[test]
+    fn parse_empty_grid() {
+        assert!(Grid::try_from("").is_err());
+        assert!(Grid::try_from("\n\n").is_err());
+    }
+
+    #[test]
+    fn neighbours_on_edges() {
+        let grid = Grid::try_from("ab\ncd").unwrap();
+        let corner = Position::new(0, 0);
+        assert_eq!(grid.neighbour(corner, Direction::North), None);
+        assert_eq!(grid.neighbour(corner, Direction::West), None);
+        assert_eq!(
+            grid.neighbour(corner, Direction::East),
+            Some(Position::new(0, 1))
+        );
+        assert_eq!(grid.neighbour(Position::new(1, 1), Direction::South), None);
+    }
+}
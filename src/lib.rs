@@ -0,0 +1,194 @@
+use anyhow::anyhow;
+use std::path::Path;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod generate;
+mod grid;
+
+pub const DAYS: &[u8] = &[
+    1, 2, 3, 4, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+];
+
+pub async fn solve(day: u8, part: u8, input_path: &Path) -> anyhow::Result<String> {
+    let content = tokio::fs::read_to_string(input_path).await?;
+    solve_input(day, part, &content)
+}
+
+pub fn solve_input(day: u8, part: u8, content: &str) -> anyhow::Result<String> {
+    let answer = match (day, part) {
+        (1, 1) => day1::part1(content)?.to_string(),
+        (1, 2) => day1::part2(content)?.to_string(),
+        (2, 1) => day2::part1(content)?.to_string(),
+        (2, 2) => day2::part2(content)?.to_string(),
+        (3, 1) => day3::part1(content)?.to_string(),
+        (3, 2) => day3::part2(content)?.to_string(),
+        (4, 1) => day4::part1(content)?.to_string(),
+        (4, 2) => day4::part2(content)?.to_string(),
+        (10, 1) => day10::part1(content)?.to_string(),
+        (10, 2) => day10::part2(content)?.to_string(),
+        (11, 1) => day11::part1(content)?.to_string(),
+        (11, 2) => day11::part2(content)?.to_string(),
+        (12, 1) => day12::part1(content)?.to_string(),
+        (12, 2) => day12::part2(content)?.to_string(),
+        (13, 1) => day13::part1(content)?.to_string(),
+        (13, 2) => day13::part2(content)?.to_string(),
+        (14, 1) => day14::part1(content)?.to_string(),
+        (14, 2) => day14::part2(content)?.to_string(),
+        (15, 1) => day15::part1(content)?.to_string(),
+        (15, 2) => day15::part2(content)?.to_string(),
+        (16, 1) => day16::part1(content)?.to_string(),
+        (16, 2) => day16::part2(content)?.to_string(),
+        (17, 1) => day17::part1(content)?.to_string(),
+        (17, 2) => day17::part2(content)?.to_string(),
+        (18, 1) => day18::part1(content)?.to_string(),
+        (18, 2) => day18::part2(content)?.to_string(),
+        (19, 1) => day19::part1(content)?.to_string(),
+        (19, 2) => day19::part2(content)?.to_string(),
+        (20, 1) => day20::part1(content)?.to_string(),
+        (20, 2) => day20::part2(content)?.to_string(),
+        (21, 1) => day21::part1(content)?.to_string(),
+        (21, 2) => day21::part2(content)?.to_string(),
+        (22, 1) => day22::part1(content)?.to_string(),
+        (22, 2) => day22::part2(content)?.to_string(),
+        (23, 1) => day23::part1(content)?.to_string(),
+        (23, 2) => day23::part2(content)?.to_string(),
+        (24, 1) => day24::part1(content)?.to_string(),
+        (24, 2) => day24::part2(content)?.to_string(),
+        (25, 1) => day25::part1(content)?.to_string(),
+        (25, 2) => day25::part2(content)?.to_string(),
+        (_, 1 | 2) => return Err(anyhow!("{} is not a valid day value", day)),
+        _ => return Err(anyhow!("{} is not a valid part value", part)),
+    };
+    Ok(answer)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    use std::path::PathBuf;
+
+    fn examples_dir(day: u8) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("examples")
+            .join(format!("day{day}"))
+    }
+
+    fn solve_with_parameter(
+        day: u8,
+        part: u8,
+        content: &str,
+        parameter: (&str, &str),
+    ) -> anyhow::Result<String> {
+        // examples that run with other constants than the real puzzle, as `key=value`
+        let answer = match (day, part, parameter) {
+            (11, _, ("factor", factor)) => {
+                day11::part_with_factor(content, factor.parse()?)?.to_string()
+            }
+            (21, 1, ("steps", steps)) => {
+                day21::part1_with_steps(content, steps.parse()?)?.to_string()
+            }
+            (21, 2, ("steps", steps)) => {
+                day21::part2_with_steps(content, steps.parse()?)?.to_string()
+            }
+            (24, 1, ("area", area)) => {
+                let (min, max) = area
+                    .split_once("..")
+                    .ok_or(anyhow!("Expected an area like min..max, got {}", area))?;
+                day24::part1_in_area(content, min.parse()?, max.parse()?)?.to_string()
+            }
+            _ => {
+                return Err(anyhow!(
+                    "Day {} part {} takes no parameter {:?}",
+                    day,
+                    part,
+                    parameter
+                ))
+            }
+        };
+        Ok(answer)
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn examples() {
+        for day in DAYS {
+            let dir = examples_dir(*day);
+            let answers = std::fs::read_to_string(dir.join("answers.txt"))
+                .unwrap_or_else(|err| panic!("Missing answers for day {day}: {err}"));
+            let lines = answers
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .collect::<Vec<&str>>();
+            // a registered day without answers would never be checked at all
+            assert!(
+                !lines.is_empty(),
+                "No expected answers for day {day} in answers.txt"
+            );
+            for line in lines {
+                let (label, expected) = line
+                    .split_once(':')
+                    .unwrap_or_else(|| panic!("Malformed answer line for day {day}: {line}"));
+                let mut label = label.split_whitespace();
+                let part = label
+                    .next()
+                    .and_then(|el| el.strip_prefix("part"))
+                    .and_then(|el| el.parse::<u8>().ok())
+                    .unwrap_or_else(|| panic!("Malformed part for day {day}: {line}"));
+                let parameter = label.next().map(|el| {
+                    el.split_once('=')
+                        .unwrap_or_else(|| panic!("Malformed parameter for day {day}: {line}"))
+                });
+                let part_input = dir.join(format!("input_part{part}.txt"));
+                let input = if part_input.exists() {
+                    part_input
+                } else {
+                    dir.join("input.txt")
+                };
+                let answer = match parameter {
+                    Some(parameter) => {
+                        let content = std::fs::read_to_string(&input).unwrap();
+                        solve_with_parameter(*day, part, &content, parameter)
+                    }
+                    None => solve(*day, part, &input).await,
+                };
+                assert!(
+                    answer.is_ok(),
+                    "Day {day} part {part} failed on {line}: {}",
+                    answer.unwrap_err()
+                );
+                assert_eq!(answer.unwrap(), expected.trim(), "Day {day}: {line}");
+            }
+        }
+    }
+
+    proptest! {
+        // the fuzz targets dig much deeper, this keeps the cheap cases from regressing
+        #[test]
+        fn hostile_inputs_never_panic(
+            day in proptest::sample::select(DAYS),
+            input in "[0-9a-zSé.#~@=<>^v|/,:{}%& \\-\\n]{0,80}|(Card [0-9]{1,5}:( [0-9]{1,2}){0,45} \\|( [0-9]{1,2}){0,45}\\n){1,3}",
+        ) {
+            for part in [1, 2] {
+                let _ = solve_input(day, part, &input);
+            }
+        }
+    }
+}
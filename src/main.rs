@@ -1,48 +1,97 @@
 use anyhow::anyhow;
+use aoc2023::{generate, solve, DAYS};
+use cli::Command;
+use client::{AocClient, Config};
 use std::path::Path;
-pub mod day1;
-pub mod day2;
-pub mod day3;
-mod day4;
+use submit::Outcome;
+mod cli;
+mod client;
+mod puzzle;
+mod scaffold;
+mod submit;
+mod watch;
 
 #[tokio::main(flavor = "current_thread")]
 async fn main() -> anyhow::Result<()> {
     env_logger::init();
-    let args: Vec<_> = std::env::args().collect();
-    if args.len() < 2 {
-        return Err(anyhow!("A day must be provided via cli"));
+    let args: Vec<_> = std::env::args().skip(1).collect();
+    match Command::try_from(args.as_slice())? {
+        Command::Run { day } => {
+            if !DAYS.contains(&day) {
+                return Err(anyhow!("{} is not a valid day value", day));
+            }
+            let input_path = format!("./input/day{day}.txt");
+            for part in [1, 2] {
+                let answer = solve(day, part, Path::new(&input_path)).await?;
+                println!("Day {day} part {part}: {answer}");
+            }
+        }
+        Command::Gen {
+            day,
+            size,
+            seed,
+            output,
+        } => {
+            let content = generate::generate(day, size, seed)?;
+            match output {
+                Some(path) => tokio::fs::write(path, content).await?,
+                None => print!("{content}"),
+            }
+        }
+        Command::New { day } => {
+            for path in scaffold::new_day(Path::new("."), day)? {
+                println!("Created {}", path.display());
+            }
+        }
+        Command::Fetch { day } => {
+            let client = AocClient::new(Config::load()?);
+            let fetched = client.fetch_input_cached(Path::new("./input"), day)?;
+            if fetched.downloaded {
+                println!("Downloaded {}", fetched.path.display());
+            } else {
+                println!("Using cached {}", fetched.path.display());
+            }
+        }
+        Command::Submit { day, part, answer } => {
+            let answer = match answer {
+                Some(answer) => answer,
+                None => solve(day, part, Path::new(&format!("./input/day{day}.txt"))).await?,
+            };
+            let client = AocClient::new(Config::load()?);
+            let history_path = Path::new("./input/attempts.txt");
+            let outcome = submit::submit(&client, history_path, day, part, &answer)?;
+            let verdict = match outcome {
+                Outcome::Correct => "is the right answer".to_string(),
+                Outcome::Wrong => "is not the right answer".to_string(),
+                Outcome::TooHigh => "is too high".to_string(),
+                Outcome::TooLow => "is too low".to_string(),
+                Outcome::RateLimited(wait) => format!("was not checked, wait {wait}s"),
+                Outcome::AlreadySolved => "was not checked, the part is already solved".to_string(),
+            };
+            println!("Day {day} part {part}: {answer} {verdict}");
+        }
+        Command::Show { day } => {
+            let client = AocClient::new(Config::load()?);
+            let html = puzzle::load_puzzle(&client, Path::new("./puzzles"), day)?;
+            let width = std::env::var("COLUMNS")
+                .ok()
+                .and_then(|el| el.parse::<usize>().ok())
+                .unwrap_or(80);
+            print!("{}", puzzle::render(&html, width)?);
+        }
+        Command::Examples { day } => {
+            let client = AocClient::new(Config::load()?);
+            let html = puzzle::load_puzzle(&client, Path::new("./puzzles"), day)?;
+            let examples_dir = Path::new("./examples").join(format!("day{day}"));
+            for path in puzzle::write_examples(&html, &examples_dir)? {
+                println!("Created {}", path.display());
+            }
+        }
+        Command::Watch { day, input } => {
+            let inputs = watch::watched_inputs(day, input);
+            let source = format!("./src/day{day}.rs");
+            watch::watch(day, &inputs, Path::new(&source))?;
+        }
     }
-    let day = args[1].parse::<u8>()?;
-    match day {
-        1 => {
-            let input_path = Path::new("./input/day1.txt");
-            let p1_answer = day1::part1(input_path).await?;
-            println!("Day 1 part 1: {p1_answer}");
-            let p2_answer = day1::part2(input_path).await?;
-            println!("Day 1 part 2: {p2_answer}");
-        }
-        2 => {
-            let input_path = Path::new("./input/day2.txt");
-            let p1_answer = day2::part1(input_path).await?;
-            println!("Day 2 part 1: {p1_answer}");
-            let p2_answer = day2::part2(input_path).await?;
-            println!("Day 2 part 2: {p2_answer}");
-        }
-        3 => {
-            let input_path = Path::new("./input/day3.txt");
-            let p1_answer = day3::part1(input_path).await?;
-            println!("Day 3 part 1: {p1_answer}");
-            let p2_answer = day3::part2(input_path).await?;
-            println!("Day 3 part 2: {p2_answer}");
-        }
-        4 => {
-            let input_path = Path::new("./input/day4.txt");
-            let p1_answer = day4::part1(input_path).await?;
-            println!("Day 4 part 1: {p1_answer}");
-            let p2_answer = day4::part2(input_path).await?;
-            println!("Day 4 part 2: {p2_answer}");
-        }
-        _ => return Err(anyhow!("{} is not a valid day value", day)),
-    };
     Ok(())
 }
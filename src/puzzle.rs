@@ -0,0 +1,256 @@
+use crate::client::AocClient;
+use anyhow::anyhow;
+use std::path::{Path, PathBuf};
+
+const ARTICLE_START: &str = "<article";
+const ARTICLE_END: &str = "</article>";
+const EXAMPLE_START: &str = "<pre><code>";
+const EXAMPLE_END: &str = "</code></pre>";
+
+pub fn load_puzzle(client: &AocClient, puzzles_dir: &Path, day: u8) -> anyhow::Result<String> {
+    // a stored page is complete once it holds both parts, until then it is refreshed
+    let path = puzzles_dir.join(format!("day{day}.html"));
+    let cached = std::fs::read_to_string(&path).ok();
+    if let Some(html) = cached.as_ref().filter(|html| articles(html).len() >= 2) {
+        return Ok(html.to_string());
+    }
+    match (client.fetch_puzzle(day), cached) {
+        (Ok(html), _) => {
+            if articles(&html).is_empty() {
+                return Err(anyhow!("No puzzle description found for day {}", day));
+            }
+            std::fs::create_dir_all(puzzles_dir)?;
+            std::fs::write(&path, &html)?;
+            Ok(html)
+        }
+        (Err(err), Some(html)) => {
+            log::warn!("Using the stored day {} description: {}", day, err);
+            Ok(html)
+        }
+        (Err(err), None) => Err(err),
+    }
+}
+
+fn articles(html: &str) -> Vec<&str> {
+    let mut found = vec![];
+    let mut rest = html;
+    while let Some(start) = rest.find(ARTICLE_START) {
+        let Some(end) = rest[start..].find(ARTICLE_END) else {
+            break;
+        };
+        found.push(&rest[start..start + end + ARTICLE_END.len()]);
+        rest = &rest[start + end + ARTICLE_END.len()..];
+    }
+    found
+}
+
+pub fn render(html: &str, width: usize) -> anyhow::Result<String> {
+    // only the puzzle text is rendered, the page navigation and sponsors are left out
+    let articles = articles(html);
+    if articles.is_empty() {
+        return Err(anyhow!("No puzzle description found in the page"));
+    }
+    Ok(html2text::from_read(articles.concat().as_bytes(), width))
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&amp;", "&")
+}
+
+fn strip_tags(text: &str) -> String {
+    // examples highlight parts of the input with <em> and the like
+    let mut stripped = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => stripped.push(c),
+            _ => {}
+        }
+    }
+    stripped
+}
+
+fn examples(article: &str) -> Vec<String> {
+    let mut found = vec![];
+    let mut rest = article;
+    while let Some(start) = rest.find(EXAMPLE_START) {
+        let block = &rest[start + EXAMPLE_START.len()..];
+        let Some(end) = block.find(EXAMPLE_END) else {
+            break;
+        };
+        found.push(decode_entities(&strip_tags(&block[..end])));
+        rest = &block[end..];
+    }
+    found
+}
+
+pub fn write_examples(html: &str, examples_dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    // the first example of each part becomes its fixture input and every other block is
+    // kept as input_2.txt, input_3.txt and so on in page order, since only the reader can
+    // tell which ones are inputs; the answers are left to the reader as well since the
+    // page highlights far more than just them
+    let mut named: Vec<(String, String)> = vec![];
+    let mut extra = 1;
+    for (part, article) in articles(html).iter().enumerate() {
+        for (indx, example) in examples(article).into_iter().enumerate() {
+            if named.iter().any(|(_, el)| *el == example) {
+                continue;
+            }
+            let name = match (part, indx) {
+                (0, 0) => "input.txt".to_string(),
+                (1, 0) => "input_part2.txt".to_string(),
+                _ => {
+                    extra += 1;
+                    format!("input_{}.txt", extra)
+                }
+            };
+            named.push((name, example));
+        }
+    }
+    if named.first().is_none_or(|(name, _)| name != "input.txt") {
+        return Err(anyhow!("No example found in the puzzle description"));
+    }
+    std::fs::create_dir_all(examples_dir)?;
+    let mut written = vec![];
+    for (name, example) in named {
+        let path = examples_dir.join(name);
+        // fixtures written by hand or by an earlier run are never overwritten
+        if std::fs::metadata(&path).is_ok_and(|el| el.len() > 0) {
+            continue;
+        }
+        std::fs::write(&path, example)?;
+        written.push(path);
+    }
+    let answers_path = examples_dir.join("answers.txt");
+    if !answers_path.exists() {
+        std::fs::write(&answers_path, "")?;
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::test::{serve, temp_dir, test_client};
+
+    const PART1: &str = "<article class=\"day-desc\"><h2>--- Day 7: Camel Cards ---</h2>
+<p>Here is an example:</p>
+<pre><code>32T3K 765
+T55J5 <em>684</em>
+</code></pre>
+<p>Compare with <code>a &lt; b</code>, the total is <code><em>6440</em></code>.</p>
+<pre><code>not an example
+</code></pre>
+</article>";
+    const PART2: &str = "<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>
+<p>Jokers are wild:</p>
+<pre><code>JJJJJ 1
+A&amp;B 2
+</code></pre>
+</article>";
+
+    const PART2_REPEATING: &str =
+        "<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>
+<pre><code>JJJJJ 1
+A&amp;B 2
+</code></pre>
+<p>The first hand again:</p>
+<pre><code>32T3K 765
+T55J5 <em>684</em>
+</code></pre>
+<pre><code>QQQJA 483
+</code></pre>
+</article>";
+
+    fn page(articles: &[&str]) -> String {
+        format!(
+            "<html><body><header>[Events] [Sponsors]</header><main>{}<p>Answer: <input/></p></main></body></html>",
+            articles.concat()
+        )
+    }
+
+    #[test]
+    fn find_articles() {
+        let html = page(&[PART1, PART2]);
+        let found = articles(&html);
+        assert_eq!(found, [PART1, PART2]);
+        assert!(articles("<main><article>unterminated</main>").is_empty());
+    }
+
+    #[test]
+    fn extract_examples() {
+        assert_eq!(
+            examples(PART1),
+            ["32T3K 765\nT55J5 684\n", "not an example\n"]
+        );
+        assert_eq!(examples(PART2), ["JJJJJ 1\nA&B 2\n"]);
+        assert!(examples("<pre><code>unterminated").is_empty());
+    }
+
+    #[test]
+    fn render_description() {
+        let text = render(&page(&[PART1, PART2]), 80).unwrap();
+        assert!(text.contains("--- Day 7: Camel Cards ---"));
+        assert!(text.contains("--- Part Two ---"));
+        assert!(text.contains("T55J5"));
+        assert!(!text.contains("Sponsors"));
+        assert!(render("<html></html>", 80).is_err());
+    }
+
+    #[test]
+    fn write_example_fixtures() {
+        let dir = temp_dir("examples");
+        let written = write_examples(&page(&[PART1]), &dir).unwrap();
+        assert_eq!(written, [dir.join("input.txt"), dir.join("input_2.txt")]);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("input.txt")).unwrap(),
+            "32T3K 765\nT55J5 684\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("input_2.txt")).unwrap(),
+            "not an example\n"
+        );
+        assert!(dir.join("answers.txt").exists());
+        // once part two unlocks only its new examples are added, repeated blocks are skipped
+        std::fs::write(dir.join("input.txt"), "edited\n").unwrap();
+        let written = write_examples(&page(&[PART1, PART2_REPEATING]), &dir).unwrap();
+        assert_eq!(
+            written,
+            [dir.join("input_part2.txt"), dir.join("input_3.txt")]
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("input.txt")).unwrap(),
+            "edited\n"
+        );
+        assert!(write_examples("<article>no examples</article>", &dir).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn store_puzzle() {
+        let dir = temp_dir("puzzles");
+        let partial = page(&[PART1]);
+        let complete = page(&[PART1, PART2]);
+        let (base_url, server) = serve(vec![(200, partial.as_str()), (200, complete.as_str())]);
+        let client = test_client(&base_url);
+        assert_eq!(load_puzzle(&client, &dir, 7).unwrap(), partial);
+        // only part one is stored, so the page is downloaded again
+        assert_eq!(load_puzzle(&client, &dir, 7).unwrap(), complete);
+        let requests = server.join().unwrap();
+        assert_eq!(requests[1].url, "/2023/day/7");
+        // nothing listens on port 1, so these only succeed from disk
+        let offline = test_client("http://127.0.0.1:1");
+        assert_eq!(load_puzzle(&offline, &dir, 7).unwrap(), complete);
+        std::fs::write(dir.join("day7.html"), &partial).unwrap();
+        assert_eq!(load_puzzle(&offline, &dir, 7).unwrap(), partial);
+        assert!(load_puzzle(&offline, &dir, 8).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,329 @@
+use anyhow::anyhow;
+use std::path::{Path, PathBuf};
+
+const DAYS_START: &str = "pub const DAYS: &[u8] = &[";
+const FALLBACK_ARM: &str = "(_, 1 | 2) =>";
+const MAX_WIDTH: usize = 100;
+
+pub fn new_day(root: &Path, day: u8) -> anyhow::Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(anyhow!("{} is not a valid day value", day));
+    }
+    let lib_path = root.join("src").join("lib.rs");
+    let module_path = root.join("src").join(format!("day{day}.rs"));
+    let examples_path = root.join("examples").join(format!("day{day}"));
+    let input_path = root.join("input").join(format!("day{day}.txt"));
+    let fuzz_manifest_path = root.join("fuzz").join("Cargo.toml");
+    let fuzz_target_path = root
+        .join("fuzz")
+        .join("fuzz_targets")
+        .join(format!("day{day}.rs"));
+    // check everything before writing anything, so a refused day leaves the tree untouched
+    for path in [&module_path, &examples_path, &fuzz_target_path] {
+        if path.exists() {
+            return Err(anyhow!(
+                "Day {} already exists: {} is in the way",
+                day,
+                path.display()
+            ));
+        }
+    }
+    let lib = std::fs::read_to_string(&lib_path)?;
+    let lib = register_day(&lib, day)?;
+
+    std::fs::write(&module_path, module_skeleton(day))?;
+    std::fs::create_dir_all(&examples_path)?;
+    std::fs::write(examples_path.join("input.txt"), "")?;
+    std::fs::write(examples_path.join("answers.txt"), "")?;
+    std::fs::write(&lib_path, lib)?;
+    let mut created = vec![module_path, examples_path, lib_path];
+    // an input already fetched is kept, an empty one is fetched again by `fetch`
+    if !input_path.exists() {
+        std::fs::create_dir_all(root.join("input"))?;
+        std::fs::write(&input_path, "")?;
+        created.push(input_path);
+    }
+    if fuzz_manifest_path.exists() {
+        let manifest = std::fs::read_to_string(&fuzz_manifest_path)?;
+        std::fs::write(&fuzz_target_path, fuzz_target(day))?;
+        std::fs::write(&fuzz_manifest_path, register_fuzz_target(&manifest, day))?;
+        created.extend([fuzz_target_path, fuzz_manifest_path]);
+    }
+    Ok(created)
+}
+
+fn module_skeleton(day: u8) -> String {
+    format!(
+        r#"use anyhow::anyhow;
+
+pub fn part1(_content: &str) -> anyhow::Result<u64> {{
+    Err(anyhow!("Day {day} part 1 is not solved yet"))
+}}
+pub fn part2(_content: &str) -> anyhow::Result<u64> {{
+    Err(anyhow!("Day {day} part 2 is not solved yet"))
+}}
+
+#[cfg(test)]
+pub mod test {{
+    use super::*;
+
+    const EXAMPLE: &str = "";
+
+    #[test]
+    #[ignore = "not solved yet"]
+    fn example_part1() {{
+        assert_eq!(part1(EXAMPLE).unwrap(), 0);
+    }}
+
+    #[test]
+    #[ignore = "not solved yet"]
+    fn example_part2() {{
+        assert_eq!(part2(EXAMPLE).unwrap(), 0);
+    }}
+}}
+"#
+    )
+}
+
+fn fuzz_target(day: u8) -> String {
+    format!(
+        r#"#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {{
+    let _ = aoc2023::day{day}::part1(input);
+    let _ = aoc2023::day{day}::part2(input);
+}});
+"#
+    )
+}
+
+fn register_fuzz_target(manifest: &str, day: u8) -> String {
+    format!(
+        "{}\n\n[[bin]]\nname = \"day{day}\"\npath = \"fuzz_targets/day{day}.rs\"\ntest = false\ndoc = false\nbench = false\n",
+        manifest.trim_end()
+    )
+}
+
+fn module_name(line: &str) -> Option<&str> {
+    line.strip_prefix("pub mod ")
+        .or(line.strip_prefix("mod "))?
+        .strip_suffix(';')
+}
+
+fn days_list(days: &[u8]) -> Vec<String> {
+    // lay the list out the way rustfmt does: on one line if it fits, else wrapped
+    let items = days
+        .iter()
+        .map(|el| el.to_string())
+        .collect::<Vec<String>>();
+    let single = format!("{}{}];", DAYS_START, items.join(", "));
+    if single.len() <= MAX_WIDTH {
+        return vec![single];
+    }
+    let mut lines = vec![DAYS_START.to_string()];
+    let mut current = String::from("   ");
+    for item in items {
+        if current.len() + item.len() + 2 > MAX_WIDTH {
+            lines.push(current.trim_end().to_string());
+            current = String::from("   ");
+        }
+        current.push(' ');
+        current.push_str(&item);
+        current.push(',');
+    }
+    lines.push(current);
+    lines.push("];".to_string());
+    lines
+}
+
+fn register_day(lib: &str, day: u8) -> anyhow::Result<String> {
+    let mut lines = lib.lines().map(String::from).collect::<Vec<String>>();
+
+    let name = format!("day{day}");
+    let mods = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(indx, line)| module_name(line).map(|module| (indx, module)))
+        .collect::<Vec<(usize, &str)>>();
+    if mods.iter().any(|(_, module)| *module == name) {
+        return Err(anyhow!("Day {} is already declared in lib.rs", day));
+    }
+    // compare day numbers, not names: day9 goes after day8 and not after day25
+    let module_day = |module: &str| module.strip_prefix("day")?.parse::<u8>().ok();
+    let before = mods
+        .iter()
+        .rev()
+        .find(|(_, module)| module_day(module).is_some_and(|el| el < day));
+    let first_day = mods.iter().find(|(_, module)| module_day(module).is_some());
+    let mod_indx = match (before, first_day) {
+        (Some((indx, _)), _) => indx + 1,
+        (None, Some((indx, _))) => *indx,
+        (None, None) => mods
+            .last()
+            .map(|(indx, _)| indx + 1)
+            .ok_or(anyhow!("Could not find the module declarations in lib.rs"))?,
+    };
+    lines.insert(mod_indx, format!("pub mod {name};"));
+
+    let days_start = lines
+        .iter()
+        .position(|line| line.starts_with(DAYS_START))
+        .ok_or(anyhow!("Could not find the DAYS list in lib.rs"))?;
+    let days_end = lines[days_start..]
+        .iter()
+        .position(|line| line.ends_with("];"))
+        .map(|offset| days_start + offset)
+        .ok_or(anyhow!("Could not find the end of the DAYS list in lib.rs"))?;
+    let mut days = lines[days_start..=days_end]
+        .join(" ")
+        .trim_start_matches(DAYS_START)
+        .trim_end_matches("];")
+        .split(',')
+        .map(|el| el.trim())
+        .filter(|el| !el.is_empty())
+        .map(|el| el.parse::<u8>())
+        .collect::<Result<Vec<u8>, _>>()?;
+    days.push(day);
+    days.sort();
+    lines.splice(days_start..=days_end, days_list(&days));
+
+    let arm_day = |line: &str| -> Option<u8> {
+        let (arm_day, _) = line.trim().strip_prefix('(')?.split_once(',')?;
+        arm_day.parse::<u8>().ok()
+    };
+    let arm_indx = lines
+        .iter()
+        .position(|line| arm_day(line).is_some_and(|el| el > day) || line.contains(FALLBACK_ARM))
+        .ok_or(anyhow!(
+            "Could not find the solve_input dispatcher in lib.rs"
+        ))?;
+    let indent = lines[arm_indx]
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect::<String>();
+    for part in [2, 1] {
+        lines.insert(
+            arm_indx,
+            format!("{indent}({day}, {part}) => {name}::part{part}(content)?.to_string(),"),
+        );
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const LIB: &str = "use anyhow::anyhow;
+pub mod day1;
+pub mod day10;
+pub mod day2;
+pub mod generate;
+mod grid;
+
+pub const DAYS: &[u8] = &[1, 2, 10];
+
+pub fn solve_input(day: u8, part: u8, content: &str) -> anyhow::Result<String> {
+    let answer = match (day, part) {
+        (1, 1) => day1::part1(content)?.to_string(),
+        (1, 2) => day1::part2(content)?.to_string(),
+        (2, 1) => day2::part1(content)?.to_string(),
+        (2, 2) => day2::part2(content)?.to_string(),
+        (10, 1) => day10::part1(content)?.to_string(),
+        (10, 2) => day10::part2(content)?.to_string(),
+        (_, 1 | 2) => return Err(anyhow!(\"{} is not a valid day value\", day)),
+        _ => return Err(anyhow!(\"{} is not a valid part value\", part)),
+    };
+    Ok(answer)
+}
+";
+
+    #[test]
+    fn register_in_order() {
+        let lib = register_day(LIB, 5).unwrap();
+        let lines = lib.lines().collect::<Vec<&str>>();
+        assert_eq!(
+            lines[3..6],
+            ["pub mod day2;", "pub mod day5;", "pub mod generate;"]
+        );
+        assert!(lib.contains("pub const DAYS: &[u8] = &[1, 2, 5, 10];"));
+        assert!(lib.contains(
+            "        (2, 2) => day2::part2(content)?.to_string(),
+        (5, 1) => day5::part1(content)?.to_string(),
+        (5, 2) => day5::part2(content)?.to_string(),
+        (10, 1) => day10::part1(content)?.to_string(),"
+        ));
+        let lib = register_day(LIB, 25).unwrap();
+        assert!(lib.contains(
+            "        (25, 2) => day25::part2(content)?.to_string(),
+        (_, 1 | 2) =>"
+        ));
+        assert!(register_day(LIB, 10).is_err());
+    }
+
+    #[test]
+    fn register_by_day_number() {
+        let lib = LIB.replace(
+            "pub mod day1;\npub mod day10;\npub mod day2;",
+            "pub mod day1;\npub mod day2;\npub mod day10;\npub mod day25;",
+        );
+        let lib = register_day(&lib, 9).unwrap();
+        let lines = lib.lines().collect::<Vec<&str>>();
+        assert_eq!(
+            lines[2..6],
+            [
+                "pub mod day2;",
+                "pub mod day9;",
+                "pub mod day10;",
+                "pub mod day25;"
+            ]
+        );
+        let lib = register_day(&LIB.replace("pub mod day1;\n", ""), 1).unwrap();
+        assert_eq!(lib.lines().nth(1), Some("pub mod day1;"));
+    }
+
+    #[test]
+    fn wrap_days_list() {
+        let days = (1..=25).collect::<Vec<u8>>();
+        let lines = days_list(&days);
+        assert_eq!(lines.first().unwrap(), DAYS_START);
+        assert_eq!(lines.last().unwrap(), "];");
+        assert!(lines.iter().all(|line| line.len() <= MAX_WIDTH));
+        assert_eq!(days_list(&[1, 2]), ["pub const DAYS: &[u8] = &[1, 2];"]);
+    }
+
+    #[test]
+    fn scaffold_day() {
+        let root = std::env::temp_dir().join(format!("aoc2023-new-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src").join("lib.rs"), LIB).unwrap();
+        let created = new_day(&root, 5).unwrap();
+        assert_eq!(created.len(), 4);
+        assert_eq!(
+            std::fs::read_to_string(root.join("input/day5.txt")).unwrap(),
+            ""
+        );
+        let module = std::fs::read_to_string(root.join("src").join("day5.rs")).unwrap();
+        assert!(module.contains("Day 5 part 1 is not solved yet"));
+        assert!(root.join("examples/day5/answers.txt").exists());
+        // a second run must not touch the existing day
+        std::fs::write(root.join("src").join("day5.rs"), "solved").unwrap();
+        assert!(new_day(&root, 5).is_err());
+        assert_eq!(
+            std::fs::read_to_string(root.join("src").join("day5.rs")).unwrap(),
+            "solved"
+        );
+        assert!(new_day(&root, 26).is_err());
+        // a puzzle input already there is kept as it is
+        std::fs::write(root.join("input/day6.txt"), "input").unwrap();
+        let created = new_day(&root, 6).unwrap();
+        assert!(!created.contains(&root.join("input/day6.txt")));
+        assert_eq!(
+            std::fs::read_to_string(root.join("input/day6.txt")).unwrap(),
+            "input"
+        );
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
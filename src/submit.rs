@@ -0,0 +1,384 @@
+use crate::client::AocClient;
+use anyhow::anyhow;
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    RateLimited(u64),
+    AlreadySolved,
+}
+
+impl Outcome {
+    pub fn from_response(html: &str) -> anyhow::Result<Self> {
+        // the verdict is the first paragraph of the page's <article>
+        let text = html
+            .split_once("<article>")
+            .map(|(_, article)| article)
+            .unwrap_or(html);
+        if text.contains("That's the right answer") {
+            Ok(Outcome::Correct)
+        } else if text.contains("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Ok(Outcome::TooHigh)
+            } else if text.contains("your answer is too low") {
+                Ok(Outcome::TooLow)
+            } else {
+                Ok(Outcome::Wrong)
+            }
+        } else if text.contains("You gave an answer too recently") {
+            let wait = text
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(wait, _)| parse_wait(wait))
+                .ok_or(anyhow!("Could not find the wait time in the response"))??;
+            Ok(Outcome::RateLimited(wait))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Ok(Outcome::AlreadySolved)
+        } else {
+            Err(anyhow!("Unexpected response to the submission: {}", text))
+        }
+    }
+
+    fn is_wrong(&self) -> bool {
+        matches!(self, Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow)
+    }
+}
+
+fn parse_wait(wait: &str) -> anyhow::Result<u64> {
+    // waits look like "4m 32s" or "32s"
+    wait.split_whitespace().try_fold(0_u64, |acc, el| {
+        let (amount, unit) = el.split_at(el.len().saturating_sub(1));
+        let seconds = match unit {
+            "h" => 3600,
+            "m" => 60,
+            "s" => 1,
+            _ => return Err(anyhow!("Unexpected wait time {}", wait)),
+        };
+        amount
+            .parse::<u64>()?
+            .checked_mul(seconds)
+            .and_then(|el| el.checked_add(acc))
+            .ok_or(anyhow!("Unexpected wait time {}", wait))
+    })
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::TooHigh => write!(f, "too_high"),
+            Outcome::TooLow => write!(f, "too_low"),
+            Outcome::RateLimited(wait) => write!(f, "rate_limited:{}", wait),
+            Outcome::AlreadySolved => write!(f, "already_solved"),
+        }
+    }
+}
+
+impl TryFrom<&str> for Outcome {
+    type Error = anyhow::Error;
+    fn try_from(str: &str) -> Result<Self, Self::Error> {
+        match str {
+            "correct" => Ok(Outcome::Correct),
+            "wrong" => Ok(Outcome::Wrong),
+            "too_high" => Ok(Outcome::TooHigh),
+            "too_low" => Ok(Outcome::TooLow),
+            "already_solved" => Ok(Outcome::AlreadySolved),
+            _ => {
+                let wait = str
+                    .strip_prefix("rate_limited:")
+                    .ok_or(anyhow!("Unexpected outcome {}", str))?;
+                Ok(Outcome::RateLimited(wait.parse::<u64>()?))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attempt {
+    time: u64,
+    day: u8,
+    part: u8,
+    answer: String,
+    outcome: Outcome,
+}
+
+impl Attempt {
+    fn try_from_line(line: &str) -> anyhow::Result<Self> {
+        let fields = line.split('\t').collect::<Vec<&str>>();
+        let [time, day, part, answer, outcome] = fields[..] else {
+            return Err(anyhow!("Expected 5 tab separated fields in {}", line));
+        };
+        Ok(Self {
+            time: time.parse::<u64>()?,
+            day: day.parse::<u8>()?,
+            part: part.parse::<u8>()?,
+            answer: answer.to_string(),
+            outcome: Outcome::try_from(outcome)?,
+        })
+    }
+}
+
+impl fmt::Display for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}",
+            self.time, self.day, self.part, self.answer, self.outcome
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct History {
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let attempts = std::fs::read_to_string(path)?
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(Attempt::try_from_line)
+            .collect::<Result<Vec<Attempt>, _>>()?;
+        Ok(Self { attempts })
+    }
+
+    pub fn record(&mut self, path: &Path, attempt: Attempt) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        writeln!(file, "{}", attempt)?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &str, now: u64) -> anyhow::Result<()> {
+        // answers are compared as numbers when both sides are numeric, since that is
+        // what the too high and too low hints refer to
+        // the rate limit applies to the whole account, not just this puzzle
+        if let Some(left) = self
+            .attempts
+            .iter()
+            .filter_map(|el| match el.outcome {
+                Outcome::RateLimited(wait) => el.time.saturating_add(wait).checked_sub(now),
+                _ => None,
+            })
+            .filter(|left| *left > 0)
+            .max()
+        {
+            return Err(anyhow!(
+                "Rate limited, wait {}s before submitting again",
+                left
+            ));
+        }
+        let value = answer.parse::<i128>().ok();
+        for attempt in self
+            .attempts
+            .iter()
+            .filter(|el| el.day == day && el.part == part)
+        {
+            let bound = value.zip(attempt.answer.parse::<i128>().ok());
+            match attempt.outcome {
+                Outcome::Correct => {
+                    return Err(anyhow!(
+                        "Day {} part {} is already solved with {}",
+                        day,
+                        part,
+                        attempt.answer
+                    ))
+                }
+                outcome if outcome.is_wrong() && attempt.answer == answer => {
+                    return Err(anyhow!("{} was already submitted and is wrong", answer))
+                }
+                Outcome::TooHigh if bound.is_some_and(|(value, high)| value >= high) => {
+                    return Err(anyhow!(
+                        "{} is not below {}, which is known to be too high",
+                        answer,
+                        attempt.answer
+                    ))
+                }
+                Outcome::TooLow if bound.is_some_and(|(value, low)| value <= low) => {
+                    return Err(anyhow!(
+                        "{} is not above {}, which is known to be too low",
+                        answer,
+                        attempt.answer
+                    ))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn submit(
+    client: &AocClient,
+    history_path: &Path,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> anyhow::Result<Outcome> {
+    let answer = answer.trim();
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        return Err(anyhow!("'{}' is not a valid answer", answer));
+    }
+    if !(1..=2).contains(&part) {
+        return Err(anyhow!("{} is not a valid part value", part));
+    }
+    let mut history = History::load(history_path)?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    history.check(day, part, answer, now)?;
+    let outcome = Outcome::from_response(&client.submit_answer(day, part, answer)?)?;
+    history.record(
+        history_path,
+        Attempt {
+            time: now,
+            day,
+            part,
+            answer: answer.to_string(),
+            outcome,
+        },
+    )?;
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::test::{serve, temp_dir, test_client};
+
+    fn page(verdict: &str) -> String {
+        format!("<html><body><main><article><p>{verdict}</p></article></main></body></html>")
+    }
+
+    fn attempt(day: u8, part: u8, answer: &str, outcome: Outcome) -> Attempt {
+        Attempt {
+            time: 1000,
+            day,
+            part,
+            answer: answer.to_string(),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn parse_responses() {
+        let cases = [
+            (
+                "That's the right answer! You are one gold star closer.",
+                Outcome::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                Outcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Outcome::TooLow,
+            ),
+            (
+                "That's not the right answer. If you're stuck, ...",
+                Outcome::Wrong,
+            ),
+            (
+                "You gave an answer too recently. You have 4m 32s left to wait.",
+                Outcome::RateLimited(272),
+            ),
+            (
+                "You gave an answer too recently. You have 9s left to wait.",
+                Outcome::RateLimited(9),
+            ),
+            (
+                "You don't seem to be solving the right level. Did you already complete it?",
+                Outcome::AlreadySolved,
+            ),
+        ];
+        for (verdict, outcome) in cases {
+            assert_eq!(
+                Outcome::from_response(&page(verdict)).unwrap(),
+                outcome,
+                "{}",
+                verdict
+            );
+        }
+        assert!(Outcome::from_response(&page("Something else")).is_err());
+    }
+
+    #[test]
+    fn parse_history_line() {
+        let line = "1000\t3\t1\t4361\trate_limited:60";
+        let parsed = Attempt::try_from_line(line).unwrap();
+        assert_eq!(parsed, attempt(3, 1, "4361", Outcome::RateLimited(60)));
+        assert_eq!(parsed.to_string(), line);
+        assert!(Attempt::try_from_line("1000\t3\t1\t4361").is_err());
+        assert!(Attempt::try_from_line("1000\t3\t1\t4361\tmaybe").is_err());
+    }
+
+    #[test]
+    fn refuse_known_answers() {
+        let history = History {
+            attempts: vec![
+                attempt(3, 1, "500", Outcome::TooHigh),
+                attempt(3, 1, "100", Outcome::TooLow),
+                attempt(3, 1, "abc", Outcome::Wrong),
+                attempt(3, 2, "42", Outcome::Correct),
+                attempt(4, 1, "7", Outcome::RateLimited(60)),
+            ],
+        };
+        assert!(history.check(3, 1, "250", 2000).is_ok());
+        assert!(history.check(3, 1, "500", 2000).is_err());
+        assert!(history.check(3, 1, "600", 2000).is_err());
+        assert!(history.check(3, 1, "100", 2000).is_err());
+        assert!(history.check(3, 1, "-5", 2000).is_err());
+        assert!(history.check(3, 1, "abc", 2000).is_err());
+        assert!(history.check(3, 2, "43", 2000).is_err());
+        assert!(history.check(4, 1, "8", 1030).is_err());
+        assert!(history.check(5, 2, "8", 1030).is_err());
+        assert!(history.check(4, 1, "8", 1060).is_ok());
+    }
+
+    #[test]
+    fn submit_against_server() {
+        let history_path = temp_dir("submit").join("attempts.txt");
+        let too_high = page("That's not the right answer; your answer is too high.");
+        let correct = page("That's the right answer! You are one gold star closer.");
+        let (base_url, server) = serve(vec![(200, too_high.as_str()), (200, correct.as_str())]);
+        let client = test_client(&base_url);
+        assert_eq!(
+            submit(&client, &history_path, 3, 1, "5000").unwrap(),
+            Outcome::TooHigh
+        );
+        // refused locally, so the server only ever sees two submissions
+        assert!(submit(&client, &history_path, 3, 1, "6000").is_err());
+        assert_eq!(
+            submit(&client, &history_path, 3, 1, "4361").unwrap(),
+            Outcome::Correct
+        );
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].url, "/2023/day/3/answer");
+        assert_eq!(requests[0].body, "level=1&answer=5000");
+        assert_eq!(requests[0].headers["cookie"], "session=53cr3t");
+        let history = History::load(&history_path).unwrap();
+        assert_eq!(history.attempts.len(), 2);
+        assert_eq!(history.attempts[1].outcome, Outcome::Correct);
+        assert!(history.check(3, 1, "4361", 0).is_err());
+        std::fs::remove_dir_all(history_path.parent().unwrap()).unwrap();
+    }
+}
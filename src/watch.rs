@@ -0,0 +1,278 @@
+use anyhow::anyhow;
+use aoc2023::{solve_input, DAYS};
+use std::any::Any;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &Path) -> Stamp {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+pub struct Watcher {
+    files: Vec<(PathBuf, Stamp)>,
+}
+
+impl Watcher {
+    pub fn new(paths: &[&Path]) -> Self {
+        let files = paths
+            .iter()
+            .map(|path| (path.to_path_buf(), stamp(path)))
+            .collect();
+        Self { files }
+    }
+
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        // polling keeps this free of platform specific file events, the files are few
+        let mut changed = vec![];
+        for (path, last) in self.files.iter_mut() {
+            let current = stamp(path);
+            if current != *last {
+                *last = current;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartRun {
+    part: u8,
+    answer: Result<String, String>,
+    elapsed: Duration,
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|el| el.to_string())
+        .or(payload.downcast_ref::<String>().cloned())
+        .unwrap_or("unknown panic".to_string())
+}
+
+fn timed_run<F>(part: u8, solve: F) -> PartRun
+where
+    F: FnOnce() -> Result<String, String> + std::panic::UnwindSafe,
+{
+    // a solver panicking on a half written input only fails its own part, the watch goes on
+    let start = Instant::now();
+    let answer = std::panic::catch_unwind(solve)
+        .unwrap_or_else(|err| Err(format!("panicked: {}", panic_message(&*err))));
+    PartRun {
+        part,
+        answer,
+        elapsed: start.elapsed(),
+    }
+}
+
+pub fn run_parts(day: u8, input: &Path) -> Vec<PartRun> {
+    let content = std::fs::read_to_string(input)
+        .map_err(|err| format!("could not read {}: {}", input.display(), err));
+    [1, 2]
+        .into_iter()
+        .map(|part| {
+            timed_run(part, || match &content {
+                Ok(content) => solve_input(day, part, content).map_err(|err| err.to_string()),
+                Err(err) => Err(err.clone()),
+            })
+        })
+        .collect()
+}
+
+pub fn watched_inputs(day: u8, extra: Option<PathBuf>) -> Vec<PathBuf> {
+    // an extra input is watched next to the puzzle input, not instead of it
+    let mut inputs = vec![PathBuf::from(format!("./input/day{day}.txt"))];
+    if let Some(extra) = extra.filter(|el| *el != inputs[0]) {
+        inputs.push(extra);
+    }
+    inputs
+}
+
+pub fn report(day: u8, previous: &[PartRun], current: &[PartRun]) -> Vec<String> {
+    current
+        .iter()
+        .map(|run| {
+            let before = previous.iter().find(|el| el.part == run.part);
+            let diff = match (before.map(|el| &el.answer), &run.answer) {
+                (None, _) => String::new(),
+                (Some(before), now) if before == now => " (unchanged)".to_string(),
+                (Some(Ok(before)), _) => format!(" (was {})", before),
+                (Some(Err(_)), _) => " (was failing)".to_string(),
+            };
+            let answer = match &run.answer {
+                Ok(answer) => answer.clone(),
+                Err(err) => format!("error: {}", err),
+            };
+            format!(
+                "Day {} part {}: {}{} in {:.2?}",
+                day, run.part, answer, diff, run.elapsed
+            )
+        })
+        .collect()
+}
+
+fn rebuild_and_restart() -> anyhow::Result<()> {
+    // source changes need a new binary, so rebuild the same profile and replace this process
+    let mut build = Command::new("cargo");
+    build.args(["build", "--quiet"]);
+    if !cfg!(debug_assertions) {
+        build.arg("--release");
+    }
+    if !build.status()?.success() {
+        return Err(anyhow!("Rebuild failed"));
+    }
+    let mut restart = Command::new(std::env::current_exe()?);
+    restart.args(std::env::args().skip(1));
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        Err(restart.exec().into())
+    }
+    #[cfg(not(unix))]
+    {
+        let status = restart.status()?;
+        std::process::exit(status.code().unwrap_or(1))
+    }
+}
+
+pub fn watch(day: u8, inputs: &[PathBuf], source: &Path) -> anyhow::Result<()> {
+    if !DAYS.contains(&day) {
+        return Err(anyhow!("{} is not a valid day value", day));
+    }
+    let mut paths = inputs.iter().map(PathBuf::as_path).collect::<Vec<&Path>>();
+    paths.push(source);
+    let mut watcher = Watcher::new(&paths);
+    let mut previous = vec![];
+    for input in inputs {
+        println!("Running {}", input.display());
+        let current = run_parts(day, input);
+        report(day, &[], &current)
+            .iter()
+            .for_each(|line| println!("{line}"));
+        previous.push(current);
+    }
+    let names = paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<String>>();
+    println!("Watching {}", names.join(", "));
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        let changed = watcher.changed();
+        if changed.iter().any(|path| path == source) {
+            println!("{} changed, rebuilding", source.display());
+            if let Err(err) = rebuild_and_restart() {
+                println!("{err}, still running the previous build");
+            }
+            continue;
+        }
+        for (input, before) in inputs.iter().zip(previous.iter_mut()) {
+            if !changed.contains(input) {
+                continue;
+            }
+            println!("{} changed", input.display());
+            let current = run_parts(day, input);
+            report(day, before, &current)
+                .iter()
+                .for_each(|line| println!("{line}"));
+            *before = current;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::test::temp_dir;
+
+    fn run(part: u8, answer: Result<&str, &str>) -> PartRun {
+        PartRun {
+            part,
+            answer: answer.map(String::from).map_err(String::from),
+            elapsed: Duration::from_micros(1500),
+        }
+    }
+
+    #[test]
+    fn detect_changes() {
+        let dir = temp_dir("watch");
+        std::fs::create_dir_all(&dir).unwrap();
+        let (input, source) = (dir.join("day1.txt"), dir.join("day1.rs"));
+        std::fs::write(&input, "1abc2\n").unwrap();
+        let mut watcher = Watcher::new(&[&input, &source]);
+        assert!(watcher.changed().is_empty());
+        std::fs::write(&input, "1abc2\npqr3stu8vwx\n").unwrap();
+        assert_eq!(watcher.changed(), [input.as_path()]);
+        assert!(watcher.changed().is_empty());
+        std::fs::write(&source, "pub fn part1() {}").unwrap();
+        std::fs::remove_file(&input).unwrap();
+        assert_eq!(watcher.changed(), [input, source]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn run_example() {
+        let dir = temp_dir("watch-run");
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("day1.txt");
+        std::fs::write(&input, "1abc2\npqr3stu8vwx\n").unwrap();
+        let runs = run_parts(1, &input);
+        assert_eq!(runs[0].answer, Ok("50".to_string()));
+        assert_eq!(runs[1].answer, Ok("50".to_string()));
+        std::fs::remove_dir_all(&dir).unwrap();
+        let runs = run_parts(1, &input);
+        assert!(runs.iter().all(|el| el.answer.is_err()));
+    }
+
+    #[test]
+    fn panics_fail_their_part() {
+        let run = timed_run(2, || panic!("index out of bounds"));
+        assert_eq!(run.part, 2);
+        assert_eq!(run.answer, Err("panicked: index out of bounds".to_string()));
+        let run = timed_run(1, || panic!("{} is not a pipe", 'x'));
+        assert_eq!(run.answer, Err("panicked: x is not a pipe".to_string()));
+    }
+
+    #[test]
+    fn extra_input_is_added() {
+        assert_eq!(watched_inputs(4, None), [PathBuf::from("./input/day4.txt")]);
+        assert_eq!(
+            watched_inputs(4, Some(PathBuf::from("big.txt"))),
+            [PathBuf::from("./input/day4.txt"), PathBuf::from("big.txt")]
+        );
+        assert_eq!(
+            watched_inputs(4, Some(PathBuf::from("./input/day4.txt"))),
+            [PathBuf::from("./input/day4.txt")]
+        );
+    }
+
+    #[test]
+    fn report_diff() {
+        let previous = [run(1, Ok("142")), run(2, Err("bad line"))];
+        let current = [run(1, Ok("142")), run(2, Ok("281"))];
+        assert_eq!(
+            report(1, &[], &previous),
+            [
+                "Day 1 part 1: 142 in 1.50ms",
+                "Day 1 part 2: error: bad line in 1.50ms"
+            ]
+        );
+        assert_eq!(
+            report(1, &previous, &current),
+            [
+                "Day 1 part 1: 142 (unchanged) in 1.50ms",
+                "Day 1 part 2: 281 (was failing) in 1.50ms"
+            ]
+        );
+        assert_eq!(
+            report(1, &current, &[run(2, Ok("280"))]),
+            ["Day 1 part 2: 280 (was 281) in 1.50ms"]
+        );
+    }
+}